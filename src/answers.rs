@@ -0,0 +1,133 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+// Known correct answers, one per line as `<year> <day> <part> <answer>`
+pub const DEFAULT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.txt");
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    Unknown,
+}
+
+#[derive(Debug)]
+pub struct Answers {
+    path: PathBuf,
+    answers: BTreeMap<(u16, u8, u8), String>,
+}
+
+impl Answers {
+    // A missing file is treated as having no answers yet
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_owned();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let mut answers = BTreeMap::new();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(4, ' ');
+            let year = fields.next().and_then(|y| y.parse::<u16>().ok());
+            let day = fields.next().and_then(|d| d.parse::<u8>().ok());
+            let part = fields.next().and_then(|p| p.parse::<u8>().ok());
+            match (year, day, part, fields.next()) {
+                (Some(year), Some(day), Some(part @ 1..=2), Some(answer)) => {
+                    answers.insert((year, day, part), answer.to_owned());
+                }
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{}:{}: invalid answer {:?}", path.display(), i + 1, line),
+                    ))
+                }
+            }
+        }
+
+        Ok(Answers { path, answers })
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, year: u16, day: u8, part: u8, answer: &str) -> Verdict {
+        match self.get(year, day, part) {
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Wrong {
+                expected: expected.to_owned(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+
+    // Stores an answer if there isn't one already, returning whether it was added
+    pub fn record(&mut self, year: u16, day: u8, part: u8, answer: &str) -> bool {
+        if self.answers.contains_key(&(year, day, part)) {
+            return false;
+        }
+        self.answers.insert((year, day, part), answer.to_owned());
+        true
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let contents: String = self
+            .answers
+            .iter()
+            .map(|((year, day, part), answer)| format!("{} {:02} {} {}\n", year, day, part, answer))
+            .collect();
+        fs::write(&self.path, contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_check_and_record() {
+        let path = std::env::temp_dir().join(format!("aoc2019_answers_{}", std::process::id()));
+        fs::write(
+            &path,
+            "# year day part answer\n2019 01 1 3271095\n2019 08 2 EBZUR\n",
+        )
+        .unwrap();
+
+        let mut answers = Answers::load(&path).unwrap();
+        assert_eq!(answers.check(2019, 1, 1, "3271095"), Verdict::Correct);
+        assert_eq!(
+            answers.check(2019, 8, 2, "EBZUK"),
+            Verdict::Wrong {
+                expected: "EBZUR".to_owned()
+            }
+        );
+        assert_eq!(answers.check(2019, 1, 2, "4903759"), Verdict::Unknown);
+
+        assert!(answers.record(2019, 1, 2, "4903759"));
+        assert!(!answers.record(2019, 1, 1, "0"));
+        answers.save().unwrap();
+
+        let reloaded = Answers::load(&path).unwrap();
+        assert_eq!(reloaded.get(2019, 1, 1), Some("3271095"));
+        assert_eq!(reloaded.get(2019, 1, 2), Some("4903759"));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        let path = std::env::temp_dir().join(format!("aoc2019_bad_{}", std::process::id()));
+        fs::write(&path, "2019 01 3 12\n").unwrap();
+        assert!(Answers::load(&path).is_err());
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,315 @@
+use std::{fmt::Write, time::Instant};
+
+use num_format::{Locale, ToFormattedString};
+
+use crate::{
+    memory::{self, Usage},
+    AnySolution, ParseError,
+};
+
+// Summary statistics over a set of timings, in nanoseconds
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub min: u128,
+    pub max: u128,
+    pub std_dev: f64,
+    // Samples outside the 1.5 * IQR fences
+    pub outliers: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub year: u16,
+    pub day: u8,
+    pub answers: [String; 2],
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+    // Heap usage of parsing and each part, when the counting allocator is installed
+    pub memory: Option<[Usage; 3]>,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[u128]) -> Self {
+        assert!(
+            !samples.is_empty(),
+            "Can't summarise an empty set of timings"
+        );
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len();
+
+        let mean = sorted.iter().sum::<u128>() as f64 / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|x| (*x as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        let (q1, q3) = (quantile(&sorted, 0.25), quantile(&sorted, 0.75));
+        let fence = 1.5 * (q3 - q1);
+        let outliers = sorted
+            .iter()
+            .filter(|x| (**x as f64) < q1 - fence || (**x as f64) > q3 + fence)
+            .count();
+
+        Stats {
+            samples: n,
+            mean,
+            median: quantile(&sorted, 0.5),
+            min: sorted[0],
+            max: sorted[n - 1],
+            std_dev: variance.sqrt(),
+            outliers,
+        }
+    }
+
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"samples\":{},\"mean\":{:.1},\"median\":{:.1},\"min\":{},\"max\":{},\"std_dev\":{:.1},\"outliers\":{}}}",
+            self.samples, self.mean, self.median, self.min, self.max, self.std_dev, self.outliers
+        )
+    }
+}
+
+// Linearly interpolated quantile of sorted samples
+fn quantile(sorted: &[u128], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    let frac = pos - lo as f64;
+    sorted[lo] as f64 + (sorted[hi] as f64 - sorted[lo] as f64) * frac
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (u128, T) {
+    let start = Instant::now();
+    let result = f();
+    (start.elapsed().as_nanos(), result)
+}
+
+// Times parsing and each part separately. Input loading isn't timed, and the parts share
+// one parsed input. Measuring memory takes one more untimed run of each.
+pub fn benchmark(
+    solution: &dyn AnySolution,
+    lines: &[String],
+    iterations: usize,
+    warmup: usize,
+    measure_memory: bool,
+) -> Result<Report, ParseError> {
+    let iterations = iterations.max(1);
+
+    for _ in 0..warmup {
+        let input = solution.parse(lines)?;
+        solution.part1(&*input);
+        solution.part2(&*input);
+    }
+
+    let parse_times = (0..iterations)
+        .map(|_| {
+            let (nanos, input) = time(|| solution.parse(lines));
+            input.map(|_| nanos)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let input = solution.parse(lines)?;
+    let (part1_times, part1_answers): (Vec<_>, Vec<_>) = (0..iterations)
+        .map(|_| time(|| solution.part1(&*input)))
+        .unzip();
+    let (part2_times, part2_answers): (Vec<_>, Vec<_>) = (0..iterations)
+        .map(|_| time(|| solution.part2(&*input)))
+        .unzip();
+
+    let memory = if measure_memory && memory::is_counting() {
+        let (input, parse) = memory::measure(|| solution.parse(lines));
+        let input = input?;
+        let (_, part1) = memory::measure(|| solution.part1(&*input));
+        let (_, part2) = memory::measure(|| solution.part2(&*input));
+        Some([parse, part1, part2])
+    } else {
+        None
+    };
+
+    Ok(Report {
+        year: solution.year(),
+        day: solution.day(),
+        answers: [part1_answers[0].clone(), part2_answers[0].clone()],
+        parse: Stats::from_samples(&parse_times),
+        part1: Stats::from_samples(&part1_times),
+        part2: Stats::from_samples(&part2_times),
+        memory,
+    })
+}
+
+impl Report {
+    pub fn to_text(&self) -> String {
+        let locale = Locale::en_GB;
+        let ns = |x: f64| format!("{}ns", (x.round() as u128).to_formatted_string(&locale));
+
+        let mut out = String::new();
+        writeln!(out, "Part 1 answer: {}", self.answers[0]).unwrap();
+        writeln!(out, "Part 2 answer: {}", self.answers[1]).unwrap();
+        writeln!(
+            out,
+            "{:<8}{:>7}{:>16}{:>16}{:>16}{:>16}{:>16}{:>10}",
+            "", "iters", "mean", "median", "min", "max", "std dev", "outliers"
+        )
+        .unwrap();
+        for (name, stats) in [
+            ("parse", &self.parse),
+            ("part 1", &self.part1),
+            ("part 2", &self.part2),
+        ] {
+            writeln!(
+                out,
+                "{:<8}{:>7}{:>16}{:>16}{:>16}{:>16}{:>16}{:>10}",
+                name,
+                stats.samples,
+                ns(stats.mean),
+                ns(stats.median),
+                ns(stats.min as f64),
+                ns(stats.max as f64),
+                ns(stats.std_dev),
+                stats.outliers
+            )
+            .unwrap();
+        }
+
+        if let Some(memory) = &self.memory {
+            let count = |x: u64| x.to_formatted_string(&locale);
+            writeln!(
+                out,
+                "{:<8}{:>16}{:>16}{:>16}",
+                "", "allocations", "allocated", "peak heap"
+            )
+            .unwrap();
+            for (name, usage) in ["parse", "part 1", "part 2"].iter().zip(memory) {
+                writeln!(
+                    out,
+                    "{:<8}{:>16}{:>16}{:>16}",
+                    name,
+                    count(usage.allocations),
+                    bytes(usage.allocated),
+                    bytes(usage.peak)
+                )
+                .unwrap();
+            }
+        }
+        out
+    }
+
+    // One line of JSON per report, so runs can be appended to a file and compared
+    pub fn to_json(&self) -> String {
+        let memory = match &self.memory {
+            Some([parse, part1, part2]) => format!(
+                ",\"memory\":{{\"parse\":{},\"part1\":{},\"part2\":{}}}",
+                usage_json(parse),
+                usage_json(part1),
+                usage_json(part2)
+            ),
+            None => String::new(),
+        };
+        format!(
+            "{{\"year\":{},\"day\":{},\"answers\":[{},{}],\"parse\":{},\"part1\":{},\"part2\":{}{}}}",
+            self.year,
+            self.day,
+            json_string(&self.answers[0]),
+            json_string(&self.answers[1]),
+            self.parse.to_json(),
+            self.part1.to_json(),
+            self.part2.to_json(),
+            memory
+        )
+    }
+}
+
+fn usage_json(usage: &Usage) -> String {
+    format!(
+        "{{\"allocations\":{},\"allocated\":{},\"peak\":{}}}",
+        usage.allocations, usage.allocated, usage.peak
+    )
+}
+
+// e.g. 512 B, 1.5 KiB, 12.0 MiB
+fn bytes(n: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if n < 1024 {
+        return format!("{} B", n);
+    }
+    let mut size = n as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_from_samples() {
+        let stats = Stats::from_samples(&[10, 12, 11, 13, 100, 12, 11]);
+        assert_eq!(stats.samples, 7);
+        assert_eq!((stats.min, stats.max), (10, 100));
+        assert_eq!(stats.median, 12.0);
+        assert!((stats.mean - 24.142857).abs() < 1e-5);
+        assert!((stats.std_dev - 30.981232).abs() < 1e-5);
+        assert_eq!(stats.outliers, 1);
+    }
+
+    #[test]
+    fn single_sample() {
+        let stats = Stats::from_samples(&[42]);
+        assert_eq!((stats.mean, stats.median, stats.std_dev), (42.0, 42.0, 0.0));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn json_escapes_answers() {
+        assert_eq!(json_string("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+    }
+
+    #[test]
+    fn json_reports_start_with_the_year_and_day() {
+        let stats = Stats::from_samples(&[1]);
+        let report = Report {
+            year: 2019,
+            day: 7,
+            answers: ["1".to_owned(), "2".to_owned()],
+            parse: stats.clone(),
+            part1: stats.clone(),
+            part2: stats,
+            memory: None,
+        };
+        assert!(report
+            .to_json()
+            .starts_with("{\"year\":2019,\"day\":7,\"answers\":[\"1\",\"2\"],"));
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(bytes(512), "512 B");
+        assert_eq!(bytes(1536), "1.5 KiB");
+        assert_eq!(bytes(12 * 1024 * 1024), "12.0 MiB");
+    }
+}
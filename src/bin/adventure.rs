@@ -0,0 +1,106 @@
+use std::{
+    error::Error,
+    io::{self, Write},
+    process,
+};
+
+use aoc2019::{
+    days::day_25::{self, Day25},
+    intcode::{ascii::Session, Status},
+    *,
+};
+
+const USAGE: &str = "\
+Usage: adventure [--play] [program file | day]
+
+Explores the ship in the day 25 text adventure, collecting the safe items and working out which of
+them get past the pressure-sensitive floor, then prints what it saw and the password. The program
+defaults to the day 25 input.
+
+Options:
+    --play    Play the game by hand, one command per line of stdin";
+
+struct Options {
+    program: String,
+    play: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut program = None;
+    let mut play = false;
+    for arg in args {
+        match arg.as_str() {
+            "--play" => play = true,
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}", flag)),
+            path if program.is_none() => program = Some(path.to_owned()),
+            _ => return Err("Only one program can be given".to_owned()),
+        }
+    }
+
+    Ok(Options {
+        program: program.unwrap_or_else(|| Day25::DAY.to_string()),
+        play,
+    })
+}
+
+fn load_mem(program: &str) -> Result<Vec<i64>, Box<dyn Error>> {
+    let lines = load_program(program)?;
+    Ok(parse::int_list(parse::single_line(&lines)?, ",")?)
+}
+
+// Passes each line of stdin to the game until it ends. Commands that never give control back
+// end the game rather than hanging it.
+fn play(mem: &[i64]) -> Result<(), Box<dyn Error>> {
+    let mut session = Session::new(mem, day_25::MAX_INSTRUCTIONS);
+    let mut command = String::new();
+    loop {
+        let (transcript, status) = session.send(&command)?;
+        print!("{}", transcript.text);
+        io::stdout().flush()?;
+        match status {
+            Status::Halted => return Ok(()),
+            Status::Running => return Err("The game is stuck in a loop".into()),
+            Status::WantsInput => {}
+        }
+
+        command.clear();
+        if io::stdin().read_line(&mut command)? == 0 {
+            return Ok(());
+        }
+        if !command.ends_with('\n') {
+            command.push('\n');
+        }
+    }
+}
+
+fn explore(mem: &[i64]) -> Result<(), Box<dyn Error>> {
+    let expedition = day_25::explore(mem)?;
+    println!("{}", expedition.transcript);
+    println!("{}", expedition);
+    println!("The password is {}", expedition.password);
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(1);
+        }
+    };
+
+    let result = load_mem(&options.program).and_then(|mem| {
+        if options.play {
+            play(&mem)
+        } else {
+            explore(&mem)
+        }
+    });
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
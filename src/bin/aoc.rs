@@ -0,0 +1,661 @@
+use std::{process, thread, time::Duration};
+
+use aoc2019::{
+    answers::{self, Answers, Verdict},
+    bench::{benchmark, Report},
+    client::Client,
+    input::CacheStatus,
+    memory::CountingAllocator,
+    parallel::{run_parallel, summary_table, DayStatus, Settings},
+    submit::{self, Outcome, SubmissionLog},
+    *,
+};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "\
+Usage: aoc [options] <day | first-last | all> [number of iterations]
+       aoc submit [--year <y>] [--answers <f>] [--log <f>] <day> <part>
+       aoc cache [--year <y>] [--event <name>] <list | check | refetch [day...] | trust | prune>
+
+Options:
+    --year <y>      Run the solutions for another year (default 2019)
+    --event <name>  Use the inputs of a named event, which are only read from its cache
+    --warmup <n>    Untimed runs before measuring (default 1)
+    --json          Print one line of JSON per day instead of a table
+    --show          Print anything else a day has to show, like the map it explored
+    --verify        Check answers against the answers file, failing on a mismatch
+    --record        Add answers that aren't in the answers file yet
+    --answers <f>   Answers file to use (default answers.txt in the crate root)
+    --input <f>     Read the input from a file, or stdin for -, instead of the cache
+    --parallel      Run the days on a thread pool and print a summary table
+    --jobs <n>      Number of threads for --parallel (default one per CPU)
+    --timeout <s>   Give up on a day after this many seconds with --parallel (default 60)
+
+Submitting posts the answer using the session in AOC_SESSION, unless submissions.txt shows
+it's already known to be wrong. Correct answers are added to the answers file.
+
+The cache commands list the cached inputs, check each one's shape and checksum, fetch
+corrupted (or the given) days again, record checksums for inputs cached before there were
+any, and remove corrupted and stray files.
+
+The input cache is in src/cache/<year> unless AOC_CACHE_DIR is set.";
+
+struct Options {
+    days: Vec<&'static dyn AnySolution>,
+    iterations: Option<usize>,
+    warmup: usize,
+    json: bool,
+    show: bool,
+    verify: bool,
+    record: bool,
+    answers: String,
+    input: InputSource,
+    // Set by --event, for inputs other than the year's own
+    event: Option<String>,
+    // Worker threads, when running days in parallel
+    jobs: Option<usize>,
+    timeout: Option<Duration>,
+}
+
+struct SubmitOptions {
+    solution: &'static dyn AnySolution,
+    part: u8,
+    answers: String,
+    log: String,
+}
+
+enum CacheCommand {
+    List,
+    Check,
+    Refetch(Vec<u8>),
+    Trust,
+    Prune,
+}
+
+fn parse_days(year: u16, arg: &str) -> Option<Vec<&'static dyn AnySolution>> {
+    if arg == "all" {
+        let days = SOLUTIONS
+            .iter()
+            .copied()
+            .filter(|s| s.year() == year)
+            .collect::<Vec<_>>();
+        return Some(days).filter(|days| !days.is_empty());
+    }
+
+    match arg.split_once('-') {
+        // Days in a range without a solution are skipped, as long as some of them have one
+        Some((first, last)) => {
+            let (first, last) = (first.parse::<u8>().ok()?, last.parse::<u8>().ok()?);
+            let days = (first..=last)
+                .filter_map(|day| solution(year, day))
+                .collect::<Vec<_>>();
+            Some(days).filter(|days| !days.is_empty())
+        }
+        None => Some(vec![solution(year, arg.parse::<u8>().ok()?)?]),
+    }
+}
+
+fn event(name: Option<&str>, year: u16) -> Event {
+    match name {
+        Some(name) => Event::named(name, year),
+        None => Event::year(year),
+    }
+}
+
+// Shown after the day's report, so parse errors have already been reported
+fn show(solution: &dyn AnySolution, lines: &[String]) {
+    if let Some(shown) = solution
+        .parse(lines)
+        .ok()
+        .and_then(|input| solution.show(&*input))
+    {
+        println!("{}", shown.trim_end());
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut days = None;
+    let mut year = input::YEAR;
+    let mut options = Options {
+        days: Vec::new(),
+        iterations: None,
+        warmup: 1,
+        json: false,
+        show: false,
+        verify: false,
+        record: false,
+        answers: answers::DEFAULT_PATH.to_owned(),
+        input: InputSource::Cache,
+        event: None,
+        jobs: None,
+        timeout: None,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => options.json = true,
+            "--show" => options.show = true,
+            "--verify" => options.verify = true,
+            "--record" => options.record = true,
+            "--input" => {
+                options.input = InputSource::from_arg(args.next().ok_or("--input needs a path")?)
+            }
+            "--answers" => options.answers = args.next().ok_or("--answers needs a path")?.clone(),
+            "--event" => options.event = Some(args.next().ok_or("--event needs a name")?.clone()),
+            "--year" => {
+                year = args
+                    .next()
+                    .and_then(|y| y.parse::<u16>().ok())
+                    .ok_or("--year needs a year")?
+            }
+            "--parallel" => {
+                let cpus = thread::available_parallelism().map_or(1, |n| n.get());
+                options.jobs = options.jobs.or(Some(cpus))
+            }
+            "--jobs" => {
+                options.jobs = Some(
+                    args.next()
+                        .and_then(|n| n.parse::<usize>().ok())
+                        .filter(|&n| n > 0)
+                        .ok_or("--jobs needs a number of threads")?,
+                )
+            }
+            "--timeout" => {
+                options.timeout = Some(
+                    args.next()
+                        .and_then(|s| s.parse::<f64>().ok())
+                        .filter(|&s| s > 0.0)
+                        .map(Duration::from_secs_f64)
+                        .ok_or("--timeout needs a number of seconds")?,
+                )
+            }
+            "--warmup" => {
+                options.warmup = args
+                    .next()
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .ok_or("--warmup needs a number of runs")?
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}", flag)),
+            arg if days.is_none() => days = Some(arg),
+            arg if options.iterations.is_none() => match arg.parse::<usize>() {
+                Ok(num) if num > 0 => options.iterations = Some(num),
+                _ => return Err(format!("Invalid number of iterations {}", arg)),
+            },
+            arg => return Err(format!("Unexpected argument {}", arg)),
+        }
+    }
+
+    let days = days.ok_or("No days given")?;
+    options.days = parse_days(year, days).ok_or(format!("No solution for {} in {}", days, year))?;
+    if options.input != InputSource::Cache && options.days.len() != 1 {
+        return Err("--input can only be used with a single day".to_owned());
+    }
+    let cached = options.input == InputSource::Cache && options.event.is_none();
+    if !cached && (options.verify || options.record) {
+        return Err("Stored answers are only for the cached puzzle input".to_owned());
+    }
+    if options.event.is_some() && options.input != InputSource::Cache {
+        return Err("--event can't be used with --input".to_owned());
+    }
+    if options.timeout.is_some() && options.jobs.is_none() {
+        return Err("--timeout only applies with --parallel".to_owned());
+    }
+    if options.show && (options.json || options.jobs.is_some()) {
+        return Err("--show can't be used with --json or --parallel".to_owned());
+    }
+    Ok(options)
+}
+
+fn parse_submit_args(args: &[String]) -> Result<SubmitOptions, String> {
+    let mut year = input::YEAR;
+    let mut answers = answers::DEFAULT_PATH.to_owned();
+    let mut log = submit::DEFAULT_LOG_PATH.to_owned();
+    let mut positional = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => answers = args.next().ok_or("--answers needs a path")?.clone(),
+            "--log" => log = args.next().ok_or("--log needs a path")?.clone(),
+            "--year" => {
+                year = args
+                    .next()
+                    .and_then(|y| y.parse::<u16>().ok())
+                    .ok_or("--year needs a year")?
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}", flag)),
+            arg => positional.push(arg),
+        }
+    }
+
+    let (day, part) = match positional[..] {
+        [day, part] => (day, part),
+        _ => return Err("submit needs a day and a part".to_owned()),
+    };
+    let solution = day
+        .parse::<u8>()
+        .ok()
+        .and_then(|d| solution(year, d))
+        .ok_or(format!("No solution for {} in {}", day, year))?;
+    let part = match part.parse::<u8>() {
+        Ok(part @ 1..=2) => part,
+        _ => return Err(format!("Invalid part {}", part)),
+    };
+
+    Ok(SubmitOptions {
+        solution,
+        part,
+        answers,
+        log,
+    })
+}
+
+fn parse_cache_args(args: &[String]) -> Result<(Event, CacheCommand), String> {
+    let mut year = input::YEAR;
+    let mut name = None;
+    let mut positional = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => {
+                year = args
+                    .next()
+                    .and_then(|y| y.parse::<u16>().ok())
+                    .ok_or("--year needs a year")?
+            }
+            "--event" => name = Some(args.next().ok_or("--event needs a name")?.as_str()),
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}", flag)),
+            arg => positional.push(arg),
+        }
+    }
+
+    let command = match positional.split_first() {
+        Some((&"list", [])) => CacheCommand::List,
+        Some((&"check", [])) => CacheCommand::Check,
+        Some((&"trust", [])) => CacheCommand::Trust,
+        Some((&"prune", [])) => CacheCommand::Prune,
+        Some((&"refetch", days)) => CacheCommand::Refetch(
+            days.iter()
+                .map(|day| match day.parse::<u8>() {
+                    Ok(day @ 1..=25) => Ok(day),
+                    _ => Err(format!("Invalid day {}", day)),
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        Some((command, _)) => return Err(format!("Unknown cache command {}", command)),
+        None => return Err("No cache command given".to_owned()),
+    };
+    Ok((event(name, year), command))
+}
+
+fn describe(status: &CacheStatus) -> String {
+    match status {
+        CacheStatus::Ok => "ok".to_owned(),
+        CacheStatus::Unverified => "no checksum".to_owned(),
+        CacheStatus::Modified => "changed since it was fetched".to_owned(),
+        CacheStatus::Invalid(problem) => format!("invalid, {}", problem),
+    }
+}
+
+fn run_cache(event: Event, command: CacheCommand) {
+    let root = input::cache_dir();
+    let cached = match input::cached_inputs(&root, &event) {
+        Ok(cached) => cached,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    let corrupted = cached
+        .iter()
+        .filter(|input| {
+            matches!(
+                input.status,
+                CacheStatus::Modified | CacheStatus::Invalid(_)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    match command {
+        CacheCommand::List => {
+            println!("{} in {}", event, event.cache_dir(&root).display());
+            for input in &cached {
+                println!(
+                    "Day {:02} {:>7} bytes  {}",
+                    input.day,
+                    input.size,
+                    describe(&input.status)
+                );
+            }
+        }
+        CacheCommand::Check => {
+            for input in &corrupted {
+                println!("Day {:02}: {}", input.day, describe(&input.status));
+            }
+            println!(
+                "{} of {} inputs are corrupted",
+                corrupted.len(),
+                cached.len()
+            );
+            if !corrupted.is_empty() {
+                process::exit(1);
+            }
+        }
+        CacheCommand::Refetch(days) => {
+            let days = if days.is_empty() {
+                corrupted.iter().map(|input| input.day).collect()
+            } else {
+                days
+            };
+            let mut failed = false;
+            for day in days {
+                match input::refetch_input(&root, &event, day) {
+                    Ok(_) => println!("Day {:02}: fetched", day),
+                    Err(e) => {
+                        eprintln!("Day {:02}: {}", day, e);
+                        failed = true;
+                    }
+                }
+            }
+            if failed {
+                process::exit(1);
+            }
+        }
+        CacheCommand::Trust => match input::trust_cached_inputs(&root, &event) {
+            Ok(days) => println!("Recorded checksums for {} inputs", days.len()),
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        CacheCommand::Prune => match input::prune_cache(&root, &event) {
+            Ok(removed) => {
+                for path in &removed {
+                    println!("Removed {}", path.display());
+                }
+                println!("Removed {} files", removed.len());
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+    }
+}
+
+fn run_submit(options: SubmitOptions) {
+    let SubmitOptions { solution, part, .. } = options;
+    let (year, day) = (solution.year(), solution.day());
+
+    let lines = match get_event_input(&Event::year(year), day) {
+        Ok(lines) => lines,
+        Err(e) => {
+            eprintln!("Day {:02}: {}", day, e);
+            process::exit(1);
+        }
+    };
+    let input = match solution.parse(&lines) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Day {:02}: {}", day, e);
+            process::exit(1);
+        }
+    };
+    let answer = match part {
+        1 => solution.part1(&*input),
+        _ => solution.part2(&*input),
+    };
+    if answer == NO_ANSWER {
+        eprintln!("Day {:02} part {} has no answer to submit", day, part);
+        process::exit(1);
+    }
+    println!("Day {:02} part {}: {}", day, part, answer);
+
+    let mut log = match SubmissionLog::load(&options.log) {
+        Ok(log) => log,
+        Err(e) => {
+            eprintln!("Couldn't load submissions: {}", e);
+            process::exit(1);
+        }
+    };
+    if let Some(outcome) = log.known(year, day, part, &answer) {
+        println!("Not submitting, already known: {}", outcome);
+        process::exit(if outcome == Outcome::Correct { 0 } else { 1 });
+    }
+
+    let outcome = match Client::from_env()
+        .and_then(|client| submit::submit(&client, year, day, part, &answer))
+    {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            eprintln!("Couldn't submit day {:02} part {}: {}", day, part, e);
+            process::exit(1);
+        }
+    };
+    println!("{}", outcome);
+
+    if let Err(e) = log.record(year, day, part, &answer, &outcome) {
+        eprintln!("Couldn't update {}: {}", options.log, e);
+        process::exit(1);
+    }
+    if outcome != Outcome::Correct {
+        process::exit(1);
+    }
+
+    let recorded = Answers::load(&options.answers).and_then(|mut answers| {
+        if answers.record(year, day, part, &answer) {
+            answers.save()?;
+        }
+        Ok(())
+    });
+    if let Err(e) = recorded {
+        eprintln!("Couldn't record the answer: {}", e);
+        process::exit(1);
+    }
+}
+
+// Checks a day's answers against the stored ones, and records them if asked to. Returns how
+// many didn't match and how many were recorded.
+fn check_answers(
+    answers: &mut Answers,
+    options: &Options,
+    year: u16,
+    report: &Report,
+) -> (usize, usize) {
+    let (mut mismatches, mut recorded) = (0, 0);
+    for (part, answer) in (1..=2).zip(&report.answers) {
+        if answer == NO_ANSWER {
+            continue;
+        }
+        let day = report.day;
+        match answers.check(year, day, part, answer) {
+            Verdict::Wrong { expected } if options.verify => {
+                eprintln!(
+                    "Day {:02} part {}: got {}, expected {}",
+                    day, part, answer, expected
+                );
+                mismatches += 1;
+            }
+            Verdict::Unknown if options.record => {
+                answers.record(year, day, part, answer);
+                recorded += 1;
+            }
+            Verdict::Unknown if options.verify => {
+                eprintln!("Day {:02} part {}: no stored answer", day, part);
+            }
+            _ => {}
+        }
+    }
+    (mismatches, recorded)
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("submit") {
+        match parse_submit_args(&args[1..]) {
+            Ok(options) => run_submit(options),
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, USAGE);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+    if args.first().map(String::as_str) == Some("cache") {
+        match parse_cache_args(&args[1..]) {
+            Ok((event, command)) => run_cache(event, command),
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, USAGE);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(1);
+        }
+    };
+
+    let mut answers = match Answers::load(&options.answers) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Couldn't load answers: {}", e);
+            process::exit(1);
+        }
+    };
+    let mut mismatches = 0;
+    let mut recorded = 0;
+    let mut failures = 0;
+
+    if let Some(jobs) = options.jobs {
+        let settings = Settings {
+            jobs,
+            timeout: options.timeout.unwrap_or(Duration::from_secs(60)),
+            iterations: options.iterations,
+            warmup: options.warmup,
+        };
+        let (input, name) = (options.input.clone(), options.event.clone());
+        let load = move |solution: &dyn AnySolution| {
+            input
+                .read(&event(name.as_deref(), solution.year()), solution.day())
+                .map_err(|e| e.to_string())
+        };
+
+        let results = run_parallel(&options.days, load, settings);
+        if !options.json {
+            print!("{}", summary_table(&results, settings.timeout));
+        }
+        for result in &results {
+            match &result.status {
+                DayStatus::Finished(report) => {
+                    if options.json {
+                        println!("{}", report.to_json());
+                    }
+                    let (wrong, new) = check_answers(&mut answers, &options, result.year, report);
+                    mismatches += wrong;
+                    recorded += new;
+                }
+                _ => failures += 1,
+            }
+        }
+    } else {
+        for (i, solution) in options.days.iter().enumerate() {
+            let lines = match options.input.read(
+                &event(options.event.as_deref(), solution.year()),
+                solution.day(),
+            ) {
+                Ok(lines) => lines,
+                Err(e) => {
+                    eprintln!("Day {:02}: {}", solution.day(), e);
+                    process::exit(1);
+                }
+            };
+            let iterations = options.iterations.unwrap_or_else(|| solution.iterations());
+            let report = match benchmark(*solution, &lines, iterations, options.warmup, true) {
+                Ok(report) => report,
+                Err(e) => {
+                    eprintln!("Day {:02}: {}", solution.day(), e);
+                    process::exit(1);
+                }
+            };
+
+            if options.json {
+                println!("{}", report.to_json());
+            } else {
+                if i > 0 {
+                    println!();
+                }
+                println!("Day {:02}", solution.day());
+                print!("{}", report.to_text());
+            }
+            if options.show {
+                show(*solution, &lines);
+            }
+
+            let (wrong, new) = check_answers(&mut answers, &options, solution.year(), &report);
+            mismatches += wrong;
+            recorded += new;
+        }
+    }
+
+    if recorded > 0 {
+        if let Err(e) = answers.save() {
+            eprintln!("Couldn't save answers: {}", e);
+            process::exit(1);
+        }
+        eprintln!("Recorded {} new answers", recorded);
+    }
+
+    if mismatches > 0 {
+        eprintln!("{} answers didn't match", mismatches);
+    }
+    if mismatches > 0 || failures > 0 {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(arg: &str) -> Option<Vec<u8>> {
+        parse_days(input::YEAR, arg).map(|days| days.iter().map(|s| s.day()).collect())
+    }
+
+    #[test]
+    fn parses_days() {
+        assert_eq!(days("7"), Some(vec![7]));
+        assert_eq!(days("3-5"), Some(vec![3, 4, 5]));
+        assert_eq!(days("seven"), None);
+
+        // Day 23 isn't solved, which only matters when it's asked for by itself
+        assert_eq!(days("22-25"), Some(vec![22, 24, 25]));
+        assert_eq!(days("23"), None);
+        assert_eq!(days("23-23"), None);
+    }
+
+    fn args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn parses_events() {
+        let (event, _) = parse_cache_args(&args("--event mirror --year 2020 list")).unwrap();
+        assert_eq!(event, Event::named("mirror", 2020));
+        let (event, _) = parse_cache_args(&args("list")).unwrap();
+        assert_eq!(event, Event::year(input::YEAR));
+
+        let options = parse_args(&args("--event mirror 1")).unwrap();
+        assert_eq!(options.event.as_deref(), Some("mirror"));
+        assert!(parse_args(&args("--event mirror --verify 1")).is_err());
+        assert!(parse_args(&args("--event mirror --input - 1")).is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{self, BufRead},
+    process,
+    str::FromStr,
+    thread,
+};
+
+use num_traits::{Num, NumCast};
+
+use aoc2019::{intcode::*, *};
+
+const USAGE: &str = "\
+Usage: intcode [options] <program file | day>
+
+Options:
+    --ascii            Read stdin as text and print ASCII output as characters
+    --word-size <n>    Use 32 or 64 bit words (default 64)
+    --trace            Print each instruction to stderr as it runs
+    --dump             Print the non-zero memory once the program halts";
+
+struct Options {
+    program: String,
+    ascii: bool,
+    word_size: u8,
+    trace: bool,
+    dump: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut program = None;
+    let mut options = Options {
+        program: String::new(),
+        ascii: false,
+        word_size: 64,
+        trace: false,
+        dump: false,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ascii" => options.ascii = true,
+            "--trace" => options.trace = true,
+            "--dump" => options.dump = true,
+            "--word-size" => {
+                options.word_size = match args.next().map(String::as_str) {
+                    Some("32") => 32,
+                    Some("64") => 64,
+                    _ => return Err("--word-size must be 32 or 64".to_owned()),
+                }
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}", flag)),
+            path if program.is_none() => program = Some(path.to_owned()),
+            _ => return Err("Only one program can be given".to_owned()),
+        }
+    }
+
+    options.program = program.ok_or("No program given")?;
+    Ok(options)
+}
+
+fn parse_words<T: FromStr>(s: &str) -> Result<Vec<T>, String> {
+    s.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            w.parse::<T>()
+                .map_err(|_| format!("Invalid number {:?}", w))
+        })
+        .collect()
+}
+
+fn run<T>(source: &str, options: &Options) -> Result<(), Box<dyn Error>>
+where
+    T: Num + Copy + Clone + PartialOrd + NumCast + Display + From<bool> + FromStr + Send + 'static,
+    u8: Into<T>,
+{
+    let mem = parse_words::<T>(source)?;
+    let (mut computer, s, r) = IntcodeComputer::with_io(mem);
+    computer.set_trace(options.trace);
+
+    // The computer is dropped at the end of the thread, which closes the output channel
+    let handle = thread::spawn(move || {
+        let result = computer.try_run().map_err(|e| e.to_string());
+        (result, computer.mem)
+    });
+
+    // Feed stdin to the computer until it runs out, or the computer halts
+    let ascii = options.ascii;
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines().map_while(Result::ok) {
+            let inputs = if ascii {
+                line.bytes().chain(Some(b'\n')).map(Into::into).collect()
+            } else {
+                match parse_words::<T>(&line) {
+                    Ok(inputs) => inputs,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        continue;
+                    }
+                }
+            };
+
+            if inputs.into_iter().any(|input| s.send(input).is_err()) {
+                break;
+            }
+        }
+    });
+
+    let mut at_line_start = true;
+    for (_, val) in r.iter() {
+        match NumCast::from(val).filter(|_| ascii) {
+            Some(c @ 0..=127u8) => {
+                print!("{}", c as char);
+                at_line_start = c == b'\n';
+            }
+            _ => {
+                if !at_line_start {
+                    println!();
+                }
+                println!("{}", val);
+                at_line_start = true;
+            }
+        }
+    }
+
+    if !at_line_start {
+        println!();
+    }
+
+    let (result, mem) = handle.join().map_err(|_| "The Intcode computer panicked")?;
+    if let Err(e) = result {
+        return Err(format!("Program stopped early: {}", e).into());
+    }
+
+    if options.dump {
+        let len = mem.iter().rposition(|x| !x.is_zero()).map_or(0, |i| i + 1);
+        for (i, chunk) in mem[..len].chunks(10).enumerate() {
+            let words = chunk.iter().map(T::to_string).collect::<Vec<_>>();
+            println!("{:>6}: {}", i * 10, words.join(","));
+        }
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(1);
+        }
+    };
+
+    // Words can be split over lines, as well as by commas
+    let result = load_program(&options.program)
+        .map_err(Into::into)
+        .and_then(|lines| {
+            let source = lines.join("\n");
+            match options.word_size {
+                32 => run::<i32>(&source, &options),
+                _ => run::<i64>(&source, &options),
+            }
+        });
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,103 @@
+use std::{error::Error, process};
+
+use aoc2019::{
+    days::day_21::{self, Day21, Mode, Outcome},
+    *,
+};
+
+const USAGE: &str = "\
+Usage: springscript [--run] [--program <file | day>] [expression]
+
+Compiles a jump condition such as \"!(A & B & C) & D\" into springscript and sends the day 21
+springdroid out with it, printing the program and the hull damage or the hull it fell into.
+Without an expression, it searches for one from scratch instead. The program defaults to the
+day 21 input.
+
+Options:
+    --run                    Use RUN and sensors A to I, rather than WALK and A to D
+    --program <file | day>   Intcode program for the springdroid";
+
+struct Options {
+    expression: Option<String>,
+    mode: Mode,
+    program: String,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        expression: None,
+        mode: Mode::Walk,
+        program: Day21::DAY.to_string(),
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--run" => options.mode = Mode::Run,
+            "--program" => {
+                options.program = args.next().ok_or("--program needs a path or day")?.clone()
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}", flag)),
+            expression if options.expression.is_none() => {
+                options.expression = Some(expression.to_owned())
+            }
+            _ => return Err("Only one expression can be given".to_owned()),
+        }
+    }
+    Ok(options)
+}
+
+fn load_mem(program: &str) -> Result<Vec<i64>, Box<dyn Error>> {
+    let lines = load_program(program)?;
+    Ok(parse::int_list(parse::single_line(&lines)?, ",")?)
+}
+
+fn run(mem: &[i64], expression: &str, mode: Mode) -> Result<(), Box<dyn Error>> {
+    let (program, outcome) = day_21::run_expression(mem, expression, mode)?;
+    print!("{}", program);
+    match outcome {
+        Outcome::Damage(damage) => {
+            println!("Hull damage: {}", damage);
+            Ok(())
+        }
+        Outcome::Fell(hull) => {
+            let hull = hull
+                .iter()
+                .map(|&solid| if solid { '#' } else { '.' })
+                .collect::<String>();
+            Err(format!("The springdroid fell into space on\n{}", hull).into())
+        }
+    }
+}
+
+fn search(mem: &[i64], mode: Mode) -> Result<(), Box<dyn Error>> {
+    let found = day_21::search(mem, mode).ok_or("No expression found that gets across")?;
+    print!("{}", day_21::compile(&found.expression, mode)?);
+    println!(
+        "Found {} after {} candidates and {} droid runs",
+        found.expression, found.candidates, found.droid_runs
+    );
+    println!("Hull damage: {}", found.damage);
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(1);
+        }
+    };
+
+    let result = load_mem(&options.program).and_then(|mem| match &options.expression {
+        Some(expression) => run(&mem, expression, options.mode),
+        None => search(&mem, options.mode),
+    });
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
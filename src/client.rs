@@ -0,0 +1,415 @@
+use std::{
+    env,
+    error::Error,
+    fmt,
+    io::{self, Read, Write},
+    net::TcpStream,
+};
+
+use native_tls::TlsConnector;
+
+// A minimal HTTP/1.0 client for talking to adventofcode.com, originally borrowed from
+// BeniotZugmeyer/RustyAdventOfCode. HTTP/1.0 keeps the server from using chunked encoding,
+// so the body is just everything after the headers.
+
+pub const DEFAULT_SERVER: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/ashvin021/AdventOfCode2019";
+
+#[derive(Debug)]
+pub enum FetchError {
+    NoSession,
+    InvalidServer(String),
+    Connect(String, io::Error),
+    Tls(String),
+    Io(io::Error),
+    MalformedResponse(String),
+    // The session cookie is missing, wrong or expired
+    NotLoggedIn,
+    Status { code: u16, reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Server {
+    pub host: String,
+    pub port: u16,
+    pub tls: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub reason: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    pub server: Server,
+    session: String,
+}
+
+trait Stream: Read + Write {}
+impl<S: Read + Write> Stream for S {}
+
+impl Server {
+    // Parses `http://host[:port]` or `https://host[:port]`
+    pub fn from_url(url: &str) -> Result<Self, FetchError> {
+        let invalid = || FetchError::InvalidServer(url.to_owned());
+        let (tls, rest) = if let Some(rest) = url.strip_prefix("https://") {
+            (true, rest)
+        } else if let Some(rest) = url.strip_prefix("http://") {
+            (false, rest)
+        } else {
+            return Err(invalid());
+        };
+
+        let rest = rest.trim_end_matches('/');
+        let (host, port) = match rest.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse::<u16>().map_err(|_| invalid())?),
+            None => (rest, if tls { 443 } else { 80 }),
+        };
+        if host.is_empty() || host.contains('/') {
+            return Err(invalid());
+        }
+
+        Ok(Server {
+            host: host.to_owned(),
+            port,
+            tls,
+        })
+    }
+}
+
+impl Client {
+    pub fn new(server: Server, session: &str) -> Self {
+        Client {
+            server,
+            session: session.to_owned(),
+        }
+    }
+
+    // Uses the session cookie in AOC_SESSION, and the server in AOC_SERVER if it's set
+    pub fn from_env() -> Result<Self, FetchError> {
+        let session = env::var("AOC_SESSION").map_err(|_| FetchError::NoSession)?;
+        let server = match env::var("AOC_SERVER") {
+            Ok(url) => Server::from_url(&url)?,
+            Err(_) => Server::from_url(DEFAULT_SERVER)?,
+        };
+        Ok(Client::new(server, session.trim()))
+    }
+
+    pub fn get(&self, path: &str) -> Result<Response, FetchError> {
+        self.request("GET", path, None)
+    }
+
+    pub fn post(&self, path: &str, form: &str) -> Result<Response, FetchError> {
+        self.request("POST", path, Some(form))
+    }
+
+    // Fetches a page that's only available when logged in, failing on any non-2xx status
+    pub fn get_text(&self, path: &str) -> Result<String, FetchError> {
+        Self::text(self.get(path)?)
+    }
+
+    // Posts a form that needs a login, failing like get_text
+    pub fn post_text(&self, path: &str, form: &str) -> Result<String, FetchError> {
+        Self::text(self.post(path, form)?)
+    }
+
+    fn text(response: Response) -> Result<String, FetchError> {
+        if response.is_login_page() {
+            return Err(FetchError::NotLoggedIn);
+        }
+        if !response.is_success() {
+            return Err(FetchError::Status {
+                code: response.status,
+                reason: response.reason,
+            });
+        }
+        Ok(response.body)
+    }
+
+    fn connect(&self) -> Result<Box<dyn Stream>, FetchError> {
+        let Server { host, port, tls } = &self.server;
+        let address = format!("{}:{}", host, port);
+        let stream = TcpStream::connect(&address).map_err(|e| FetchError::Connect(address, e))?;
+
+        if !tls {
+            return Ok(Box::new(stream));
+        }
+
+        let connector = TlsConnector::new().map_err(|e| FetchError::Tls(e.to_string()))?;
+        let stream = connector
+            .connect(host, stream)
+            .map_err(|e| FetchError::Tls(e.to_string()))?;
+        Ok(Box::new(stream))
+    }
+
+    fn request(
+        &self,
+        method: &str,
+        path: &str,
+        form: Option<&str>,
+    ) -> Result<Response, FetchError> {
+        let mut stream = self.connect()?;
+
+        let mut request = format!(
+            "\
+             {} {} HTTP/1.0\r\n\
+             Host: {}\r\n\
+             User-Agent: {}\r\n\
+             Cookie: session={}\r\n\
+             ",
+            method, path, self.server.host, USER_AGENT, self.session
+        );
+        if let Some(form) = form {
+            request += &format!(
+                "Content-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n\r\n{}",
+                form.len(),
+                form
+            );
+        } else {
+            request += "\r\n";
+        }
+
+        stream
+            .write_all(request.as_bytes())
+            .map_err(FetchError::Io)?;
+
+        let mut raw = Vec::new();
+        if let Err(e) = stream.read_to_end(&mut raw) {
+            // Some servers close TLS connections without a close_notify
+            if raw.is_empty() || e.kind() != io::ErrorKind::UnexpectedEof {
+                return Err(FetchError::Io(e));
+            }
+        }
+
+        Response::parse(&String::from_utf8_lossy(&raw))
+    }
+}
+
+impl Response {
+    pub fn parse(raw: &str) -> Result<Self, FetchError> {
+        let malformed = |why: &str| FetchError::MalformedResponse(why.to_owned());
+
+        let (head, body) = raw
+            .split_once("\r\n\r\n")
+            .or_else(|| raw.split_once("\n\n"))
+            .ok_or_else(|| malformed("no end of headers"))?;
+        let mut lines = head.lines();
+
+        // e.g. HTTP/1.1 404 Not Found
+        let status_line = lines.next().ok_or_else(|| malformed("empty response"))?;
+        let mut parts = status_line.splitn(3, ' ');
+        if !parts.next().unwrap_or("").starts_with("HTTP/") {
+            return Err(malformed(status_line));
+        }
+        let status = parts
+            .next()
+            .and_then(|code| code.parse::<u16>().ok())
+            .ok_or_else(|| malformed(status_line))?;
+        let reason = parts.next().unwrap_or("").trim().to_owned();
+
+        let headers = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_owned()))
+            .collect();
+
+        Ok(Response {
+            status,
+            reason,
+            headers,
+            body: body.to_owned(),
+        })
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let name = name.to_lowercase();
+        self.headers
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    // Without a valid session the server either refuses outright, or sends its login page
+    pub fn is_login_page(&self) -> bool {
+        let body = self.body.to_lowercase();
+        body.contains("please log in")
+            || body.contains("[log in]")
+            || (self.status == 302 && self.header("location").is_some_and(|l| l.contains("auth")))
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSession => write!(f, "AOC_SESSION isn't set"),
+            Self::InvalidServer(url) => write!(
+                f,
+                "Invalid server {:?}, expected http(s)://host[:port]",
+                url
+            ),
+            Self::Connect(address, e) => write!(f, "Couldn't connect to {}: {}", address, e),
+            Self::Tls(e) => write!(f, "TLS error: {}", e),
+            Self::Io(e) => write!(f, "Error talking to the server: {}", e),
+            Self::MalformedResponse(why) => {
+                write!(f, "Malformed response from the server: {}", why)
+            }
+            Self::NotLoggedIn => {
+                write!(f, "Not logged in, AOC_SESSION is probably wrong or expired")
+            }
+            Self::Status { code, reason } => write!(f, "Server responded with {} {}", code, reason),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{io::BufRead, io::BufReader, net::TcpListener, thread};
+
+    use super::*;
+
+    // Serves one canned response per connection, returning the requests it received
+    pub(crate) fn mock_server(responses: Vec<String>) -> (Server, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    request += &line;
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                request += &String::from_utf8(body).unwrap();
+
+                stream.write_all(response.as_bytes()).unwrap();
+                requests.push(request);
+            }
+            requests
+        });
+
+        let server = Server {
+            host: "127.0.0.1".to_owned(),
+            port,
+            tls: false,
+        };
+        (server, handle)
+    }
+
+    pub(crate) fn response(status: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+    }
+
+    #[test]
+    fn parses_server_urls() {
+        let server = Server::from_url("https://adventofcode.com").unwrap();
+        assert_eq!(
+            (server.host.as_str(), server.port, server.tls),
+            ("adventofcode.com", 443, true)
+        );
+
+        let server = Server::from_url("http://127.0.0.1:8080/").unwrap();
+        assert_eq!(
+            (server.host.as_str(), server.port, server.tls),
+            ("127.0.0.1", 8080, false)
+        );
+
+        assert!(Server::from_url("adventofcode.com").is_err());
+        assert!(Server::from_url("http://localhost:http").is_err());
+    }
+
+    #[test]
+    fn parses_responses() {
+        let response =
+            Response::parse("HTTP/1.1 404 Not Found\r\nServer: Apache\r\n\r\n404 Not Found")
+                .unwrap();
+        assert_eq!(response.status, 404);
+        assert_eq!(response.reason, "Not Found");
+        assert_eq!(response.header("server"), Some("Apache"));
+        assert_eq!(response.body, "404 Not Found");
+        assert!(!response.is_success());
+
+        assert!(Response::parse("").is_err());
+        assert!(Response::parse("garbage\r\n\r\n").is_err());
+    }
+
+    #[test]
+    fn sends_session_and_reads_body() {
+        let (server, handle) = mock_server(vec![response("200 OK", "1,2,3\n")]);
+        let client = Client::new(server, "abc123");
+
+        assert_eq!(client.get_text("/2019/day/2/input").unwrap(), "1,2,3\n");
+
+        let requests = handle.join().unwrap();
+        assert!(requests[0].starts_with("GET /2019/day/2/input HTTP/1.0\r\n"));
+        assert!(requests[0].contains("Cookie: session=abc123\r\n"));
+        assert!(requests[0].contains("Host: 127.0.0.1\r\n"));
+    }
+
+    #[test]
+    fn detects_expired_sessions() {
+        let (server, handle) = mock_server(vec![
+            response(
+                "400 Bad Request",
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+            response(
+                "200 OK",
+                "<html><a href=\"/2019/auth/login\">[Log In]</a></html>",
+            ),
+        ]);
+        let client = Client::new(server, "expired");
+
+        assert!(matches!(
+            client.get_text("/2019/day/1/input"),
+            Err(FetchError::NotLoggedIn)
+        ));
+        assert!(matches!(
+            client.get_text("/2019/day/1/input"),
+            Err(FetchError::NotLoggedIn)
+        ));
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn reports_error_statuses() {
+        let (server, handle) = mock_server(vec![response("500 Internal Server Error", "oops")]);
+        let client = Client::new(server, "abc123");
+
+        match client.get_text("/2019/day/1/input") {
+            Err(FetchError::Status { code, reason }) => {
+                assert_eq!((code, reason.as_str()), (500, "Internal Server Error"))
+            }
+            other => panic!("expected a status error, got {:?}", other),
+        }
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,48 @@
+use itertools::iterate;
+
+use crate::*;
+
+fn part01(input: &[u32]) -> u32 {
+    input.iter().map(|x| x / 3 - 2).filter(|&x| x > 0).sum()
+}
+
+fn part02(input: &[u32]) -> u32 {
+    let calculate_fuel = |x: &i32| *x / 3 - 2;
+    input
+        .iter()
+        .map(|&m| {
+            let fuel = calculate_fuel(&(m as i32));
+            iterate(fuel, calculate_fuel)
+                .take_while(|&f| f > 0)
+                .sum::<i32>() as u32
+        })
+        .sum()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const ITERATIONS: usize = 100;
+
+    type Input = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(lines: &[String]) -> Result<Vec<u32>, ParseError> {
+        parse::ints_per_line(lines)
+    }
+
+    fn part1(nums: &Vec<u32>) -> u32 {
+        part01(nums)
+    }
+
+    fn part2(nums: &Vec<u32>) -> u32 {
+        part02(nums)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    crate::example_tests!(super::Day01);
+}
@@ -0,0 +1,49 @@
+use crate::intcode::{
+    symbolic::{SymbolicComputer, Target},
+    *,
+};
+use crate::*;
+
+fn part01(computer: &mut IntcodeComputer<i32>) -> i32 {
+    computer.mem[1] = 12;
+    computer.mem[2] = 2;
+    computer.run();
+    computer.mem[0]
+}
+
+fn part02(mem: &[i32]) -> i32 {
+    const TARGET: i32 = 19690720;
+
+    // Solve for noun and verb symbolically, rather than trying all 10,000 pairs
+    let mut computer = SymbolicComputer::new(mem.to_owned());
+    let noun = computer.unknown_cell(1, 0..=99);
+    let verb = computer.unknown_cell(2, 0..=99);
+
+    match computer.solve(Target::Cell(0), TARGET) {
+        Ok(solution) => 100 * solution.values[noun] + solution.values[verb],
+        Err(_) => -1,
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const ITERATIONS: usize = 100;
+
+    type Input = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(lines: &[String]) -> Result<Vec<i32>, ParseError> {
+        parse::int_list(parse::single_line(lines)?, ",")
+    }
+
+    fn part1(mem: &Vec<i32>) -> i32 {
+        part01(&mut IntcodeComputer::new(mem.clone()))
+    }
+
+    fn part2(mem: &Vec<i32>) -> i32 {
+        part02(mem)
+    }
+}
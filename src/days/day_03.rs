@@ -1,14 +1,14 @@
-use std::{convert::TryInto, str::FromStr};
+use std::convert::TryInto;
 
 use itertools::Itertools;
 
-use aoc2019::*;
+use crate::*;
 
 #[derive(Debug, Copy, Clone)]
 struct WireSection(Direction, i32);
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-struct HorizontalSegment {
+pub struct HorizontalSegment {
     x1: i32,
     x2: i32,
     y: i32,
@@ -16,7 +16,7 @@ struct HorizontalSegment {
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-struct VerticalSegment {
+pub struct VerticalSegment {
     y1: i32,
     y2: i32,
     x: i32,
@@ -31,34 +31,17 @@ enum Direction {
     Down,
 }
 
-#[derive(Debug, Copy, Clone)]
-struct ParseError;
-
-impl FromStr for Direction {
-    type Err = ParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let dir = match &s[0..1] {
-            "L" => Direction::Left,
-            "R" => Direction::Right,
-            "U" => Direction::Up,
-            "D" => Direction::Down,
-            _ => {
-                return Err(ParseError);
-            }
-        };
-        Ok(dir)
-    }
-}
-
-impl FromStr for WireSection {
-    type Err = ParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let magnitude = i32::from_str(&s[1..]).map_err(|_| ParseError)?;
-        let dir = Direction::from_str(&s[0..1])?;
-        Ok(WireSection(dir, magnitude))
-    }
+// e.g. R75
+fn parse_section(section: parse::Span) -> Result<WireSection, ParseError> {
+    let (dir, magnitude) = section.split_first()?;
+    let dir = match dir {
+        'L' => Direction::Left,
+        'R' => Direction::Right,
+        'U' => Direction::Up,
+        'D' => Direction::Down,
+        _ => return Err(section.error(format!("expected L, R, U or D, found {:?}", dir))),
+    };
+    Ok(WireSection(dir, magnitude.parse()?))
 }
 
 fn to_segments(path: &[WireSection]) -> (Vec<HorizontalSegment>, Vec<VerticalSegment>) {
@@ -97,7 +80,7 @@ fn to_segments(path: &[WireSection]) -> (Vec<HorizontalSegment>, Vec<VerticalSeg
                 y += d;
             }
         };
-        steps += d.abs() as u32;
+        steps += d.unsigned_abs();
     }
 
     (horizontal, vertical)
@@ -110,7 +93,7 @@ fn intersect_segments(h: &HorizontalSegment, v: &VerticalSegment) -> Option<i32>
     ys.sort_unstable();
 
     if (xs[0]..=xs[1]).contains(&v.x) && (ys[0]..=ys[1]).contains(&h.y) {
-        Some(v.x + h.y)
+        Some(v.x.abs() + h.y.abs())
     } else {
         None
     }
@@ -123,15 +106,15 @@ fn intersect_segments_steps(h: &HorizontalSegment, v: &VerticalSegment) -> Optio
     ys.sort_unstable();
 
     if (xs[0]..=xs[1]).contains(&v.x) && (ys[0]..=ys[1]).contains(&h.y) {
-        let h_steps = h.steps + ((v.x - h.x1).abs() as u32);
-        let v_steps = v.steps + ((h.y - v.y1).abs() as u32);
+        let h_steps = h.steps + (v.x - h.x1).unsigned_abs();
+        let v_steps = v.steps + (h.y - v.y1).unsigned_abs();
         Some(h_steps + v_steps)
     } else {
         None
     }
 }
 
-fn part01(segments: &[(Vec<HorizontalSegment>, Vec<VerticalSegment>); 2]) -> i32 {
+fn part01(segments: &Segments) -> i32 {
     let (h1, v1) = &segments[0];
     let (h2, v2) = &segments[1];
     h1.iter()
@@ -143,7 +126,7 @@ fn part01(segments: &[(Vec<HorizontalSegment>, Vec<VerticalSegment>); 2]) -> i32
         .unwrap()
 }
 
-fn part02(segments: &[(Vec<HorizontalSegment>, Vec<VerticalSegment>); 2]) -> u32 {
+fn part02(segments: &Segments) -> u32 {
     let (h1, v1) = &segments[0];
     let (h2, v2) = &segments[1];
     h1.iter()
@@ -155,21 +138,75 @@ fn part02(segments: &[(Vec<HorizontalSegment>, Vec<VerticalSegment>); 2]) -> u32
         .unwrap()
 }
 
-fn day_03() -> (i32, u32) {
-    let raw: Vec<String> = get_input(3).collect();
-    let segments = raw
-        .iter()
-        .map(|l| {
-            l.split(',')
-                .map(|s| s.parse::<WireSection>().unwrap())
-                .collect::<Vec<_>>()
+pub type Segments = [(Vec<HorizontalSegment>, Vec<VerticalSegment>); 2];
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const ITERATIONS: usize = 100;
+
+    type Input = Segments;
+    type Part1 = i32;
+    type Part2 = u32;
+
+    fn parse(lines: &[String]) -> Result<Segments, ParseError> {
+        let wires = parse::lines(lines)
+            .map(|line| {
+                let sections = line
+                    .split(",")
+                    .map(parse_section)
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(to_segments(&sections))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let count = wires.len();
+        wires.try_into().map_err(|_| ParseError {
+            line: count.min(2) + 1,
+            column: 1,
+            message: format!("expected 2 wires, found {}", count),
         })
-        .map(|w| to_segments(&w))
-        .collect::<Vec<_>>()
-        .try_into()
-        .unwrap();
+    }
+
+    fn part1(segments: &Segments) -> i32 {
+        part01(segments)
+    }
 
-    (part01(&segments), part02(&segments))
+    fn part2(segments: &Segments) -> u32 {
+        part02(segments)
+    }
 }
 
-timed_main!(100, day_03());
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!(super::Day03);
+
+    #[test]
+    fn reports_bad_sections() {
+        let wires = |first: &str| vec![first.to_owned(), "U7,R6,D4,L4".to_owned()];
+        let position = |e: ParseError| (e.line, e.column);
+
+        assert_eq!(
+            <Day03 as Solution>::parse(&wires("R8,,L5"))
+                .map_err(position)
+                .err(),
+            Some((1, 4))
+        );
+        assert_eq!(
+            <Day03 as Solution>::parse(&wires("R8,X5"))
+                .map_err(position)
+                .err(),
+            Some((1, 4))
+        );
+        assert_eq!(
+            <Day03 as Solution>::parse(&wires("R8,U"))
+                .map_err(position)
+                .err(),
+            Some((1, 5))
+        );
+        assert!(<Day03 as Solution>::parse(&wires("R8")[..1]).is_err());
+    }
+}
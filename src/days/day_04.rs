@@ -2,7 +2,7 @@ use std::convert::TryInto;
 
 use itertools::any;
 
-use aoc2019::*;
+use crate::*;
 
 fn to_digits(x: &u32) -> Vec<u8> {
     fn inner(n: u32, ds: &mut Vec<u8>) {
@@ -63,17 +63,32 @@ fn part02(digits: &[Vec<u8>]) -> u32 {
         .unwrap()
 }
 
-fn day_04() -> (u32, u32) {
-    let nums: Vec<_> = get_input(4)
-        .next()
-        .unwrap()
-        .split('-')
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect();
-    let digits = (nums[0]..=nums[1])
-        .map(|x| to_digits(&x))
-        .collect::<Vec<_>>();
-    (part01(&digits), part02(&digits))
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const ITERATIONS: usize = 100;
+
+    type Input = Vec<Vec<u8>>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(lines: &[String]) -> Result<Vec<Vec<u8>>, ParseError> {
+        let (low, high) = parse::single_line(lines)?.split_once("-")?;
+        let (low, high) = (low.parse::<u32>()?, high.parse::<u32>()?);
+        Ok((low..=high).map(|x| to_digits(&x)).collect())
+    }
+
+    fn part1(digits: &Vec<Vec<u8>>) -> u32 {
+        part01(digits)
+    }
+
+    fn part2(digits: &Vec<Vec<u8>>) -> u32 {
+        part02(digits)
+    }
 }
 
-timed_main!(100, day_04());
+#[cfg(test)]
+mod tests {
+    crate::example_tests!(super::Day04);
+}
@@ -0,0 +1,51 @@
+use std::thread;
+
+use crate::{intcode::*, *};
+
+fn part01(mem: &[i32]) -> i32 {
+    let (mut computer, s, r) = IntcodeComputer::with_io(mem.to_owned());
+
+    thread::spawn(move || {
+        computer.run();
+    });
+
+    s.send(1).unwrap();
+
+    // The diagnostic code comes after the test results
+    r.iter().last().unwrap().1
+}
+
+fn part02(mem: &[i32]) -> i32 {
+    let (mut computer, s, r) = IntcodeComputer::with_io(mem.to_owned());
+
+    thread::spawn(move || {
+        computer.run();
+    });
+
+    s.send(5).unwrap();
+
+    r.recv().unwrap().1
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const ITERATIONS: usize = 100;
+
+    type Input = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(lines: &[String]) -> Result<Vec<i32>, ParseError> {
+        parse::int_list(parse::single_line(lines)?, ",")
+    }
+
+    fn part1(mem: &Vec<i32>) -> i32 {
+        part01(mem)
+    }
+
+    fn part2(mem: &Vec<i32>) -> i32 {
+        part02(mem)
+    }
+}
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, convert::TryInto};
 
-use aoc2019::*;
+use crate::*;
 
 fn depth(map: &HashMap<String, String>, start: &str) -> usize {
     let mut parent = start;
@@ -51,19 +51,35 @@ fn part02(map: &HashMap<String, String>) -> u32 {
         .unwrap()
 }
 
-fn day_06() -> (u32, u32) {
-    let map = get_input(6)
-        .map(|l| {
-            let mut pair = l.split(')').map(str::to_string);
-            let value = pair.next().unwrap();
-            let key = pair.next().unwrap();
-            (key, value)
-        })
-        .collect::<HashMap<_, _>>();
-
-    let p1 = part01(&map);
-    let p2 = part02(&map);
-    (p1, p2)
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const ITERATIONS: usize = 100;
+
+    type Input = HashMap<String, String>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(lines: &[String]) -> Result<HashMap<String, String>, ParseError> {
+        parse::lines(lines)
+            .map(|line| {
+                let (centre, orbiter) = parse::key_value(line, ")")?;
+                Ok((orbiter.as_str().to_owned(), centre.as_str().to_owned()))
+            })
+            .collect()
+    }
+
+    fn part1(map: &HashMap<String, String>) -> u32 {
+        part01(map)
+    }
+
+    fn part2(map: &HashMap<String, String>) -> u32 {
+        part02(map)
+    }
 }
 
-timed_main!(100, day_06());
+#[cfg(test)]
+mod tests {
+    crate::example_tests!(super::Day06);
+}
@@ -10,7 +10,7 @@ use std::{
 
 use itertools::Itertools;
 
-use aoc2019::{intcode::*, *};
+use crate::{intcode::*, *};
 
 fn part01(mem: &[i32]) -> Result<i32, Box<dyn Error>> {
     let mut max = 0;
@@ -27,7 +27,6 @@ fn part01(mem: &[i32]) -> Result<i32, Box<dyn Error>> {
             s.send(output)?;
 
             let (_, out) = r.recv()?;
-            // println!("Output ({}): {}", i, out);
             output = out;
         }
         max = cmp::max(max, output);
@@ -74,7 +73,6 @@ fn part02(mem: &[i32]) -> Result<i32, Box<dyn Error>> {
             s[3].send(r[2].recv()?.1)?;
             s[4].send(r[3].recv()?.1)?;
             let (_, out) = r[4].recv()?;
-            // println!("Output ({}): {}", i, out);
             output = out;
         }
 
@@ -84,13 +82,25 @@ fn part02(mem: &[i32]) -> Result<i32, Box<dyn Error>> {
     Ok(max)
 }
 
-fn day_07() -> (i32, i32) {
-    let raw: String = get_input(7).next().unwrap();
-    let mem: Vec<i32> = raw.split(',').map(|i| i.parse::<i32>().unwrap()).collect();
+pub struct Day07;
 
-    let p1 = part01(&mem).unwrap();
-    let p2 = part02(&mem).unwrap();
-    (p1, p2)
-}
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const ITERATIONS: usize = 100;
+
+    type Input = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(lines: &[String]) -> Result<Vec<i32>, ParseError> {
+        parse::int_list(parse::single_line(lines)?, ",")
+    }
 
-timed_main!(100, day_07());
+    fn part1(mem: &Vec<i32>) -> i32 {
+        part01(mem).unwrap()
+    }
+
+    fn part2(mem: &Vec<i32>) -> i32 {
+        part02(mem).unwrap()
+    }
+}
@@ -1,6 +1,6 @@
 use std::convert::TryInto;
 
-use aoc2019::*;
+use crate::{ocr::read_letters, *};
 
 fn part01(encoded: &[u8], width: u8, height: u8) -> u32 {
     let least_zeroes = encoded
@@ -30,15 +30,12 @@ fn part02(encoded: &[u8], width: u8, height: u8) -> String {
         .map(|ps| *ps.iter().find(|p| **p != 2).unwrap())
         .collect();
 
-    for row in decoded.chunks(25) {
-        let chars: String = row
-            .iter()
-            .map(|i| if *i == 1 { '#' } else { ' ' })
-            .collect();
-        println!("{:?}", chars);
-    }
+    let rows: Vec<Vec<bool>> = decoded
+        .chunks(width.into())
+        .map(|row| row.iter().map(|i| *i == 1).collect())
+        .collect();
 
-    String::from("EBZUR")
+    read_letters(&rows).expect("couldn't read the letters in the image")
 }
 
 fn transpose<T: Clone>(grid: Vec<Vec<T>>) -> Vec<Vec<T>> {
@@ -53,23 +50,30 @@ fn transpose<T: Clone>(grid: Vec<Vec<T>>) -> Vec<Vec<T>> {
     transposed
 }
 
-fn day_08() -> (u32, String) {
-    let encoded = get_input(8)
-        .next()
-        .unwrap()
-        .chars()
-        .map(|c| c.to_digit(10).unwrap() as u8)
-        .collect::<Vec<_>>();
+const WIDTH: u8 = 25;
+const HEIGHT: u8 = 6;
 
-    const WIDTH: u8 = 25;
-    const HEIGHT: u8 = 6;
+pub struct Day08;
 
-    let p1 = part01(&encoded, WIDTH, HEIGHT);
-    let p2 = part02(&encoded, WIDTH, HEIGHT);
-    (p1, p2)
-}
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input = Vec<u8>;
+    type Part1 = u32;
+    type Part2 = String;
+
+    fn parse(lines: &[String]) -> Result<Vec<u8>, ParseError> {
+        parse::digits(parse::single_line(lines)?)
+    }
+
+    fn part1(encoded: &Vec<u8>) -> u32 {
+        part01(encoded, WIDTH, HEIGHT)
+    }
 
-timed_main!(1, day_08());
+    fn part2(encoded: &Vec<u8>) -> String {
+        part02(encoded, WIDTH, HEIGHT)
+    }
+}
 
 #[cfg(test)]
 mod tests {
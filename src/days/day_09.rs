@@ -0,0 +1,50 @@
+use std::thread;
+
+use crate::{intcode::*, *};
+
+fn part01(mem: &[i64]) -> i64 {
+    let (mut computer, s, r) = IntcodeComputer::with_io(mem.to_owned());
+
+    thread::spawn(move || {
+        computer.run();
+    });
+
+    s.send(1).unwrap();
+
+    // Anything before the keycode is an opcode that isn't working
+    r.iter().last().unwrap().1
+}
+
+fn part02(mem: &[i64]) -> i64 {
+    let (mut computer, s, r) = IntcodeComputer::with_io(mem.to_owned());
+
+    thread::spawn(move || {
+        computer.run();
+    });
+
+    s.send(2).unwrap();
+
+    r.recv().unwrap().1
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<i64>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Vec<i64>, ParseError> {
+        parse::int_list(parse::single_line(lines)?, ",")
+    }
+
+    fn part1(mem: &Vec<i64>) -> i64 {
+        part01(mem)
+    }
+
+    fn part2(mem: &Vec<i64>) -> i64 {
+        part02(mem)
+    }
+}
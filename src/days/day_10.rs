@@ -1,10 +1,8 @@
-#![feature(slice_group_by)]
-
 use std::{cmp::Ordering, collections::HashSet};
 
 use itertools::Itertools;
 
-use aoc2019::*;
+use crate::*;
 
 fn part01(asteroids: &HashSet<(i8, i8)>) -> (u32, (i8, i8)) {
     let mut max_coords = (0, (0, 0));
@@ -12,6 +10,7 @@ fn part01(asteroids: &HashSet<(i8, i8)>) -> (u32, (i8, i8)) {
     for (i, j) in asteroids {
         let count = asteroids
             .iter()
+            .filter(|p| **p != (*i, *j))
             // Get relative positions to (i, j)
             .map(|(x, y)| (x - i, y - j))
             // Unique by unit circle angle from (i, j), so direction
@@ -50,7 +49,7 @@ fn part02(asteroids: &HashSet<(i8, i8)>, station_coords: (i8, i8)) -> i32 {
 
     // Group the asteroids by angle
     let mut groups = relative
-        .group_by(|a, b| (get_angle(a) - get_angle(b)).abs() < f64::EPSILON)
+        .chunk_by(|a, b| (get_angle(a) - get_angle(b)).abs() < f64::EPSILON)
         .map(|ps| ps.iter())
         .collect_vec();
 
@@ -80,27 +79,46 @@ fn get_angle((x, y): &(i8, i8)) -> f64 {
     (f64::from(*x)).atan2(f64::from(*y))
 }
 
-fn day_10() -> (u32, i32) {
-    let asteroids: HashSet<(i8, i8)> = get_input(10)
-        .enumerate()
-        .map(|(j, s)| {
-            s.chars()
-                .enumerate()
-                .filter_map(|(i, c)| {
-                    if c == '#' {
-                        Some((i as i8, j as i8))
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>()
-        })
-        .flatten()
-        .collect();
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const ITERATIONS: usize = 100;
 
-    let (count, max_coord) = part01(&asteroids);
-    let p2 = part02(&asteroids, max_coord);
-    (count, p2)
+    type Input = HashSet<(i8, i8)>;
+    type Part1 = u32;
+    type Part2 = i32;
+
+    fn parse(lines: &[String]) -> Result<HashSet<(i8, i8)>, ParseError> {
+        let grid = parse::grid(lines, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        Ok(grid
+            .iter()
+            .enumerate()
+            .flat_map(|(j, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, asteroid)| **asteroid)
+                    .map(move |(i, _)| (i as i8, j as i8))
+            })
+            .collect())
+    }
+
+    fn part1(asteroids: &HashSet<(i8, i8)>) -> u32 {
+        part01(asteroids).0
+    }
+
+    fn part2(asteroids: &HashSet<(i8, i8)>) -> i32 {
+        // The station goes wherever part 1 found the best view
+        let (_, station_coords) = part01(asteroids);
+        part02(asteroids, station_coords)
+    }
 }
 
-timed_main!(100, day_10());
+#[cfg(test)]
+mod tests {
+    crate::example_tests!(super::Day10);
+}
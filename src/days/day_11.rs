@@ -7,7 +7,7 @@ use std::{
     thread,
 };
 
-use aoc2019::{intcode::*, *};
+use crate::{intcode::*, ocr::read_letters, *};
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Colour {
@@ -115,27 +115,44 @@ fn part02(mem: &[i64]) -> String {
     // Run the intcode instrutions
     bot.run(mem);
 
-    let mut output = [[' '; 45]; 8];
-
-    for ((x, y), colour) in bot.painted_panels {
-        output[(y + 1) as usize][(x + 1) as usize] =
-            if colour == Colour::White { '#' } else { ' ' };
-    }
-
-    for line in output.iter().map(|l| l.iter().collect::<String>()) {
-        println!("{}", line);
+    // Crop to the white panels
+    let white = bot
+        .painted_panels
+        .iter()
+        .filter(|(_, colour)| **colour == Colour::White)
+        .map(|(position, _)| *position)
+        .collect::<Vec<_>>();
+    let min_x = white.iter().map(|(x, _)| *x).min().unwrap();
+    let min_y = white.iter().map(|(_, y)| *y).min().unwrap();
+    let max_x = white.iter().map(|(x, _)| *x).max().unwrap();
+    let max_y = white.iter().map(|(_, y)| *y).max().unwrap();
+
+    let mut output = vec![vec![false; (max_x - min_x + 1) as usize]; (max_y - min_y + 1) as usize];
+    for (x, y) in white {
+        output[(y - min_y) as usize][(x - min_x) as usize] = true;
     }
 
-    "LPZKLGHR".to_owned()
+    read_letters(&output).expect("couldn't read the letters on the hull")
 }
 
-fn day_11() -> (u32, String) {
-    let raw: String = get_input(11).next().unwrap();
-    let mem: Vec<i64> = raw.split(',').map(|i| i.parse::<i64>().unwrap()).collect();
+pub struct Day11;
 
-    let p1 = part01(&mem);
-    let p2 = part02(&mem);
-    (p1, p2)
-}
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Vec<i64>;
+    type Part1 = u32;
+    type Part2 = String;
 
-timed_main!(1, day_11());
+    fn parse(lines: &[String]) -> Result<Vec<i64>, ParseError> {
+        parse::int_list(parse::single_line(lines)?, ",")
+    }
+
+    fn part1(mem: &Vec<i64>) -> u32 {
+        part01(mem)
+    }
+
+    fn part2(mem: &Vec<i64>) -> String {
+        part02(mem)
+    }
+}
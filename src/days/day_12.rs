@@ -1,9 +1,9 @@
-use std::{collections::HashMap, convert::TryInto};
+use std::collections::HashMap;
 
 use gcd::Gcd;
 use itertools::{izip, Itertools};
 
-use aoc2019::*;
+use crate::*;
 
 #[derive(Debug, Clone)]
 struct Moon {
@@ -108,7 +108,7 @@ fn part02(moons: &mut MoonSystem) -> u64 {
         let mut xs: Vec<(i32, i32)> = Vec::new();
 
         loop {
-            if prevcounts.get(&xs).is_some() {
+            if prevcounts.contains_key(&xs) {
                 break;
             }
 
@@ -131,24 +131,75 @@ fn part02(moons: &mut MoonSystem) -> u64 {
     lcm(periods.to_vec())
 }
 
-fn day_12() -> (i32, u64) {
-    let start_positions: Vec<[i32; 3]> = get_input(12)
-        .map(|l| {
-            let vec = l
-                .split('=')
-                .filter_map(|val| {
-                    let val_str: String =
-                        val.chars().take_while(|c| *c != ',' && *c != '>').collect();
-                    val_str.parse::<i32>().ok()
-                })
-                .collect_vec();
-            vec.try_into().unwrap()
-        })
-        .collect_vec();
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Vec<[i32; 3]>;
+    type Part1 = i32;
+    type Part2 = u64;
+
+    // e.g. <x=-1, y=0, z=2>
+    fn parse(lines: &[String]) -> Result<Vec<[i32; 3]>, ParseError> {
+        parse::lines(lines)
+            .map(|line| {
+                let mut fields = line.strip_prefix("<")?.strip_suffix(">")?.split(", ");
+                let mut position = [0; 3];
+                for (axis, name) in position.iter_mut().zip(&["x", "y", "z"]) {
+                    let field = fields
+                        .next()
+                        .ok_or_else(|| line.error(format!("expected a value for {}", name)))?;
+                    let (key, value) = parse::key_value(field, "=")?;
+                    if key.as_str() != *name {
+                        return Err(key.error(format!(
+                            "expected {}, found {:?}",
+                            name,
+                            key.as_str()
+                        )));
+                    }
+                    *axis = value.parse()?;
+                }
+                match fields.next() {
+                    Some(extra) => Err(extra.error("expected only x, y and z")),
+                    None => Ok(position),
+                }
+            })
+            .collect()
+    }
 
-    let p1 = part01(&mut MoonSystem::new(&start_positions));
-    let p2 = part02(&mut MoonSystem::new(&start_positions));
-    (p1, p2)
+    fn part1(start_positions: &Vec<[i32; 3]>) -> i32 {
+        part01(&mut MoonSystem::new(start_positions))
+    }
+
+    fn part2(start_positions: &Vec<[i32; 3]>) -> u64 {
+        part02(&mut MoonSystem::new(start_positions))
+    }
 }
 
-timed_main!(1, day_12());
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!(super::Day12, part2);
+
+    // The examples only give part 1's energy after 10 and 100 steps
+    #[test]
+    fn energy_after_steps() {
+        let examples = [
+            ([[-1, 0, 2], [2, -10, -7], [4, -8, 8], [3, 5, -1]], 10, 179),
+            (
+                [[-8, -10, 0], [5, 5, 10], [2, -7, 3], [9, -8, -3]],
+                100,
+                1940,
+            ),
+        ];
+        for (positions, steps, energy) in examples.iter() {
+            let mut moons = MoonSystem::new(positions);
+            for _ in 0..*steps {
+                moons.apply_step();
+            }
+            assert_eq!(moons.total_energy(), *energy);
+        }
+    }
+}
@@ -2,10 +2,6 @@ use std::{
     collections::HashMap,
     convert::TryInto,
     error::Error,
-    sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
-    },
     thread::{self, JoinHandle},
 };
 
@@ -13,7 +9,7 @@ use crossbeam::channel::{Receiver, Sender};
 use enum_primitive_derive::Primitive;
 use num_traits::FromPrimitive;
 
-use aoc2019::{intcode::*, *};
+use crate::{intcode::*, *};
 
 #[derive(Debug, Copy, Clone, Primitive, PartialEq)]
 pub enum GameTile {
@@ -34,17 +30,15 @@ pub struct ArcadeCabinet {
     ball: (i32, i32),
     paddle: (i32, i32),
 
-    // fields for Intcode computer, IO, and termination
+    // fields for Intcode computer and IO
     computer: Option<IntcodeComputer<i64>>,
     sender: Sender<i64>,
     receiver: Receiver<(i64, i64)>,
-    stopped: Arc<AtomicBool>,
 }
 
 impl ArcadeCabinet {
     fn new(mem: &[i64]) -> Self {
         let (computer, sender, receiver) = IntcodeComputer::with_io(mem.to_vec());
-        let stopped = Arc::new(AtomicBool::new(false));
         ArcadeCabinet {
             screen: HashMap::new(),
             ball: (0, 0),
@@ -53,7 +47,6 @@ impl ArcadeCabinet {
             computer: Some(computer),
             sender,
             receiver,
-            stopped,
         }
     }
 
@@ -63,10 +56,9 @@ impl ArcadeCabinet {
             None => panic!("The computer has already been started."),
         };
 
-        let thread_stopped = self.stopped.clone();
+        // The computer is dropped when it halts, which closes its output channel
         thread::spawn(move || {
             computer.run();
-            thread_stopped.store(true, Ordering::SeqCst);
         })
     }
 
@@ -75,9 +67,9 @@ impl ArcadeCabinet {
         let handle = self.start_computer();
         let r = &self.receiver;
 
-        // Read tiles while the computer is still running
-        while !self.stopped.load(Ordering::SeqCst) {
-            if let Ok(ArcadeScreenIn::Tile(x, y, tile)) = Self::receive_screen_in(r) {
+        // Read tiles until the computer halts and its output has been drained
+        while let Ok(screen_in) = Self::receive_screen_in(r) {
+            if let ArcadeScreenIn::Tile(x, y, tile) = screen_in {
                 self.screen.insert((x, y), tile);
             }
         }
@@ -111,15 +103,9 @@ impl ArcadeCabinet {
 
         let mut score = 0;
 
-        // Read output and react while the computer is still running
-        while !self.stopped.load(Ordering::SeqCst) {
-            // Read the data and check error condition
-            let data = Self::receive_screen_in(r);
-            if data.is_err() {
-                continue;
-            }
-
-            match data.unwrap() {
+        // Read output and react until the computer halts and its output has been drained
+        while let Ok(data) = Self::receive_screen_in(r) {
+            match data {
                 ArcadeScreenIn::Tile(x, y, tile) => {
                     // Update paddle
                     if let GameTile::HorizontalPaddle = tile {
@@ -161,13 +147,24 @@ fn part02(mem: &[i64]) -> i64 {
     arcade_cabinet.play_game()
 }
 
-fn day_13() -> (u32, i64) {
-    let raw: String = get_input(13).next().unwrap();
-    let mem: Vec<i64> = raw.split(',').map(|i| i.parse::<i64>().unwrap()).collect();
+pub struct Day13;
 
-    let p1 = part01(&mem);
-    let p2 = part02(&mem);
-    (p1, p2)
-}
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Vec<i64>;
+    type Part1 = u32;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Vec<i64>, ParseError> {
+        parse::int_list(parse::single_line(lines)?, ",")
+    }
+
+    fn part1(mem: &Vec<i64>) -> u32 {
+        part01(mem)
+    }
 
-timed_main!(1, day_13());
+    fn part2(mem: &Vec<i64>) -> i64 {
+        part02(mem)
+    }
+}
@@ -4,12 +4,11 @@ use std::{
 };
 
 use itertools::Itertools;
-use regex::Regex;
 
-use aoc2019::*;
+use crate::*;
 
 #[derive(Debug)]
-struct Chemical {
+pub struct Chemical {
     name: String,
     qty: u64,
 }
@@ -24,12 +23,7 @@ fn ores_needed(equations: &HashMap<String, (u64, Vec<Chemical>)>, qty: u64) -> u
         qty,
     });
 
-    loop {
-        let target = match needed.pop_front() {
-            Some(chemical) => chemical,
-            None => break,
-        };
-
+    while let Some(target) = needed.pop_front() {
         if target.name == "ORE" {
             num_ores += target.qty;
             continue;
@@ -38,7 +32,7 @@ fn ores_needed(equations: &HashMap<String, (u64, Vec<Chemical>)>, qty: u64) -> u
         let required = deduct_from_inventory(&target, &mut inventory);
         let (ingredients, leftovers) = lookup_reaction(&target.name, required, equations);
 
-        needed.extend(ingredients.into_iter());
+        needed.extend(ingredients);
         *inventory.entry(target.name).or_insert(0) += leftovers;
     }
 
@@ -49,8 +43,8 @@ fn deduct_from_inventory(target: &Chemical, inventory: &mut HashMap<String, u64>
     let mut default = 0;
     let available = inventory.get_mut(&target.name).unwrap_or(&mut default);
 
-    let required = target.qty.checked_sub(*available).unwrap_or(0);
-    *available = (*available).checked_sub(target.qty).unwrap_or(0);
+    let required = target.qty.saturating_sub(*available);
+    *available = (*available).saturating_sub(target.qty);
     required
 }
 
@@ -60,7 +54,7 @@ fn lookup_reaction(
     equations: &HashMap<String, (u64, Vec<Chemical>)>,
 ) -> (Vec<Chemical>, u64) {
     let (output_qty, ingredients) = equations.get(target).unwrap();
-    let factor = (qty + (output_qty - 1)) / output_qty;
+    let factor = qty.div_ceil(*output_qty);
 
     let scaled = ingredients
         .iter()
@@ -93,36 +87,51 @@ fn part02(equations: &HashMap<String, (u64, Vec<Chemical>)>, available_ore: u64)
     mid
 }
 
-fn day_14() -> (u64, u64) {
-    let input_re = Regex::new("(\\d+) (\\w+),? ").unwrap();
-    let output_re = Regex::new("=> (\\d+) (\\w+)").unwrap();
+pub type Equations = HashMap<String, (u64, Vec<Chemical>)>;
 
-    let parse_line = |line: &str| {
-        let input = input_re
-            .captures_iter(line)
-            .map(|cap| Chemical {
-                name: cap[2].to_owned(),
-                qty: cap[1].parse::<u64>().unwrap(),
-            })
-            .collect_vec();
-        let output = output_re
-            .captures(line)
-            .map(|cap| (cap[1].parse::<u64>().unwrap(), cap[2].to_owned()))
-            .unwrap();
+const AVAILABLE_ORE: u64 = 1_000_000_000_000;
 
-        (output.1, (output.0, input))
-    };
+pub struct Day14;
 
-    let map: HashMap<_, _> = get_input(14)
-        .map(|line| parse_line(&line))
-        .into_iter()
-        .collect();
+impl Solution for Day14 {
+    const DAY: u8 = 14;
 
-    let p1 = part01(&map);
+    type Input = Equations;
+    type Part1 = u64;
+    type Part2 = u64;
 
-    const AVAILABLE_ORE: u64 = 1_000_000_000_000;
-    let p2 = part02(&map, AVAILABLE_ORE);
-    (p1, p2)
+    // e.g. 7 A, 1 E => 1 FUEL
+    fn parse(lines: &[String]) -> Result<Equations, ParseError> {
+        let chemical = |span| {
+            parse::term(span).map(|(qty, name)| Chemical {
+                name: name.as_str().to_owned(),
+                qty,
+            })
+        };
+
+        parse::lines(lines)
+            .map(|line| {
+                let (inputs, output) = line.split_once(" => ")?;
+                let inputs = inputs
+                    .split(", ")
+                    .map(chemical)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let output = chemical(output)?;
+                Ok((output.name, (output.qty, inputs)))
+            })
+            .collect()
+    }
+
+    fn part1(equations: &Equations) -> u64 {
+        part01(equations)
+    }
+
+    fn part2(equations: &Equations) -> u64 {
+        part02(equations, AVAILABLE_ORE)
+    }
 }
 
-timed_main!(1, day_14());
+#[cfg(test)]
+mod tests {
+    crate::example_tests!(super::Day14);
+}
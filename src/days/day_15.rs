@@ -0,0 +1,260 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    thread::{self, JoinHandle},
+};
+
+use crossbeam::channel::{Receiver, Sender};
+
+use crate::{intcode::*, *};
+
+type Position = (i32, i32);
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Tile {
+    Wall,
+    Open,
+    Oxygen,
+}
+
+// Movement commands, numbered as the droid expects them
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Direction {
+    North = 1,
+    South = 2,
+    West = 3,
+    East = 4,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ];
+
+    fn reverse(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+            Direction::East => Direction::West,
+        }
+    }
+
+    fn step(self, (x, y): Position) -> Position {
+        match self {
+            Direction::North => (x, y - 1),
+            Direction::South => (x, y + 1),
+            Direction::West => (x - 1, y),
+            Direction::East => (x + 1, y),
+        }
+    }
+}
+
+struct Droid {
+    sender: Sender<i64>,
+    receiver: Receiver<(i64, i64)>,
+    handle: JoinHandle<()>,
+}
+
+impl Droid {
+    fn new(mem: &[i64]) -> Self {
+        let (mut computer, sender, receiver) = IntcodeComputer::with_io(mem.to_vec());
+        // The program never halts, so it stops with an error once the droid's input is closed
+        let handle = thread::spawn(move || {
+            let _ = computer.try_run();
+        });
+        Droid {
+            sender,
+            receiver,
+            handle,
+        }
+    }
+
+    // Tries to move, returning what's in the way or what the droid moved onto
+    fn go(&self, direction: Direction) -> Tile {
+        self.sender.send(direction as i64).unwrap();
+        match self.receiver.recv().unwrap().1 {
+            0 => Tile::Wall,
+            1 => Tile::Open,
+            2 => Tile::Oxygen,
+            status => panic!("unknown status code {}", status),
+        }
+    }
+
+    fn shut_down(self) {
+        drop(self.sender);
+        self.handle.join().unwrap();
+    }
+}
+
+// The area the droid's explored, with the droid starting at (0, 0)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShipMap {
+    tiles: HashMap<Position, Tile>,
+}
+
+impl ShipMap {
+    pub fn oxygen(&self) -> Option<Position> {
+        self.tiles
+            .iter()
+            .find(|(_, tile)| **tile == Tile::Oxygen)
+            .map(|(position, _)| *position)
+    }
+
+    // Fewest moves to each reachable position
+    pub fn distances_from(&self, start: Position) -> HashMap<Position, usize> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+        distances.insert(start, 0);
+        queue.push_back(start);
+
+        while let Some(position) = queue.pop_front() {
+            let distance = distances[&position];
+            for direction in &Direction::ALL {
+                let next = direction.step(position);
+                let open = matches!(self.tiles.get(&next), Some(Tile::Open | Tile::Oxygen));
+                if open && !distances.contains_key(&next) {
+                    distances.insert(next, distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distances
+    }
+}
+
+// Walks every reachable square depth first, backing up after each dead end so the droid always
+// knows where it is
+fn explore_from(droid: &Droid, tiles: &mut HashMap<Position, Tile>, position: Position) {
+    for &direction in &Direction::ALL {
+        let next = direction.step(position);
+        if tiles.contains_key(&next) {
+            continue;
+        }
+
+        let tile = droid.go(direction);
+        tiles.insert(next, tile);
+        if tile != Tile::Wall {
+            explore_from(droid, tiles, next);
+            droid.go(direction.reverse());
+        }
+    }
+}
+
+pub fn explore(mem: &[i64]) -> ShipMap {
+    let droid = Droid::new(mem);
+    let mut tiles = HashMap::new();
+    tiles.insert((0, 0), Tile::Open);
+    explore_from(&droid, &mut tiles, (0, 0));
+    droid.shut_down();
+    ShipMap { tiles }
+}
+
+// Parsing has already checked that the droid found the oxygen system
+fn part01(map: &ShipMap) -> usize {
+    let oxygen = map.oxygen().unwrap();
+    map.distances_from((0, 0))[&oxygen]
+}
+
+// Oxygen spreads one square a minute, so the fill time is the distance to the furthest square
+fn part02(map: &ShipMap) -> usize {
+    let oxygen = map.oxygen().unwrap();
+    map.distances_from(oxygen).values().copied().max().unwrap()
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = ShipMap;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    // The droid explores the whole area once, and both parts search the map it made
+    fn parse(lines: &[String]) -> Result<ShipMap, ParseError> {
+        let mem = parse::int_list(parse::single_line(lines)?, ",")?;
+        let map = explore(&mem);
+        match map.oxygen() {
+            Some(_) => Ok(map),
+            None => Err(parse::Span::new("", 1).error("the droid didn't find the oxygen system")),
+        }
+    }
+
+    fn part1(map: &ShipMap) -> usize {
+        part01(map)
+    }
+
+    fn part2(map: &ShipMap) -> usize {
+        part02(map)
+    }
+
+    fn show(map: &ShipMap) -> Option<String> {
+        Some(map.to_string())
+    }
+}
+
+// Walls are #, open squares ., the oxygen system O and the droid's starting point D, with
+// unexplored squares left blank
+impl fmt::Display for ShipMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let xs = self.tiles.keys().map(|(x, _)| *x);
+        let ys = self.tiles.keys().map(|(_, y)| *y);
+        let (min_x, max_x) = (xs.clone().min().unwrap_or(0), xs.max().unwrap_or(0));
+        let (min_y, max_y) = (ys.clone().min().unwrap_or(0), ys.max().unwrap_or(0));
+
+        for y in min_y..=max_y {
+            let row = (min_x..=max_x)
+                .map(|x| match self.tiles.get(&(x, y)) {
+                    _ if (x, y) == (0, 0) => 'D',
+                    Some(Tile::Wall) => '#',
+                    Some(Tile::Open) => '.',
+                    Some(Tile::Oxygen) => 'O',
+                    None => ' ',
+                })
+                .collect::<String>();
+            writeln!(f, "{}", row.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The oxygen example from the puzzle, with the droid at the top left open square
+    const EXAMPLE: &str = " ##\n#D.##\n#.#..#\n#.O.#\n ###\n";
+
+    fn example_map() -> ShipMap {
+        let mut tiles = HashMap::new();
+        for (y, row) in EXAMPLE.lines().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                let tile = match c {
+                    '#' => Tile::Wall,
+                    '.' | 'D' => Tile::Open,
+                    'O' => Tile::Oxygen,
+                    _ => continue,
+                };
+                tiles.insert((x as i32 - 1, y as i32 - 1), tile);
+            }
+        }
+        ShipMap { tiles }
+    }
+
+    #[test]
+    fn fills_with_oxygen() {
+        let map = example_map();
+        assert_eq!(map.oxygen(), Some((1, 2)));
+        assert_eq!(part01(&map), 3);
+        assert_eq!(part02(&map), 4);
+    }
+
+    #[test]
+    fn renders_the_map() {
+        assert_eq!(example_map().to_string(), EXAMPLE);
+    }
+}
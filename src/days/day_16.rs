@@ -0,0 +1,179 @@
+use std::{error::Error, fmt};
+
+use crate::*;
+
+const BASE_PATTERN: [i64; 4] = [0, 1, 0, -1];
+const PHASES: usize = 100;
+const REPEATS: usize = 10_000;
+// The message offset is given by the first 7 digits, and the message is 8 digits long
+const OFFSET_DIGITS: usize = 7;
+const MESSAGE_LENGTH: usize = 8;
+
+// The signal, and where the message starts once it's repeated
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signal {
+    digits: Vec<u8>,
+    offset: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct OffsetError {
+    offset: usize,
+    length: usize,
+}
+
+// One phase of the transmission. Output digit i weights the signal by the pattern with each
+// element repeated i + 1 times, skipping the first weight, so the weights come in runs and each
+// run is a difference of prefix sums.
+fn phase(signal: &[u8], pattern: &[i64]) -> Vec<u8> {
+    let mut prefix = vec![0; signal.len() + 1];
+    for (i, digit) in signal.iter().enumerate() {
+        prefix[i + 1] = prefix[i] + *digit as i64;
+    }
+
+    (1..=signal.len())
+        .map(|width| {
+            let total = (width - 1..signal.len())
+                .step_by(width)
+                .zip(pattern.iter().cycle().skip(1))
+                .filter(|(_, weight)| **weight != 0)
+                .map(|(start, weight)| {
+                    let end = (start + width).min(signal.len());
+                    weight * (prefix[end] - prefix[start])
+                })
+                .sum::<i64>();
+            (total.abs() % 10) as u8
+        })
+        .collect()
+}
+
+fn to_string(digits: &[u8]) -> String {
+    digits.iter().map(|d| char::from(b'0' + d)).collect()
+}
+
+fn part01(signal: &[u8]) -> String {
+    let output = (0..PHASES).fold(signal.to_vec(), |signal, _| phase(&signal, &BASE_PATTERN));
+    to_string(&output[..MESSAGE_LENGTH])
+}
+
+// The message has to be in the second half of the repeated signal for part 2 to find it
+fn message_offset(digits: &[u8]) -> Result<usize, OffsetError> {
+    let length = digits.len() * REPEATS;
+    let offset = digits
+        .iter()
+        .take(OFFSET_DIGITS)
+        .fold(0, |offset, digit| offset * 10 + *digit as usize);
+    if offset < length / 2 || offset + MESSAGE_LENGTH > length {
+        return Err(OffsetError { offset, length });
+    }
+    Ok(offset)
+}
+
+// In the second half of the signal every weight from a digit onwards is 1 and every one before
+// it is 0, so each output digit is the sum of the digits after it. Only the digits from the
+// message offset onwards are needed.
+fn part02(signal: &Signal) -> String {
+    let digits = &signal.digits;
+    let mut tail = (signal.offset..digits.len() * REPEATS)
+        .map(|i| digits[i % digits.len()])
+        .collect::<Vec<_>>();
+    for _ in 0..PHASES {
+        let mut sum = 0;
+        for digit in tail.iter_mut().rev() {
+            sum = (sum + *digit) % 10;
+            *digit = sum;
+        }
+    }
+    to_string(&tail[..MESSAGE_LENGTH])
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = Signal;
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(lines: &[String]) -> Result<Signal, ParseError> {
+        let line = parse::single_line(lines)?;
+        let digits = parse::digits(line)?;
+        if digits.len() < MESSAGE_LENGTH {
+            return Err(line.error(format!(
+                "expected a signal of at least {} digits",
+                MESSAGE_LENGTH
+            )));
+        }
+        let offset = message_offset(&digits).map_err(|e| line.error(e.to_string()))?;
+        Ok(Signal { digits, offset })
+    }
+
+    fn part1(signal: &Signal) -> String {
+        part01(&signal.digits)
+    }
+
+    fn part2(signal: &Signal) -> String {
+        part02(signal)
+    }
+}
+
+impl fmt::Display for OffsetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Message offset {} isn't in the second half of the {} digit signal, \
+             so the suffix sums don't apply",
+            self.offset, self.length
+        )
+    }
+}
+
+impl Error for OffsetError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The part 1 examples have offsets that part 2 can't use, so they aren't valid input
+    crate::example_tests!(super::Day16, part2);
+
+    #[test]
+    fn part1_examples() {
+        for (signal, message) in [
+            ("80871224585914546619083218645595", "24176176"),
+            ("19617804207202209144916044189917", "73745418"),
+            ("69317163492948606335995924319873", "52432133"),
+        ] {
+            let digits = signal.bytes().map(|b| b - b'0').collect::<Vec<_>>();
+            assert_eq!(part01(&digits), message);
+        }
+    }
+
+    #[test]
+    fn runs_phases() {
+        let phases = (0..4)
+            .scan(vec![1, 2, 3, 4, 5, 6, 7, 8], |signal, _| {
+                *signal = phase(signal, &BASE_PATTERN);
+                Some(to_string(signal))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(phases, ["48226158", "34040438", "03415518", "01029498"]);
+    }
+
+    #[test]
+    fn rejects_offsets_in_the_first_half() {
+        let signal = vec![0, 0, 0, 0, 0, 0, 5, 1, 2, 3];
+        assert_eq!(
+            message_offset(&signal),
+            Err(OffsetError {
+                offset: 5,
+                length: 100_000
+            })
+        );
+
+        let lines = ["0000005123".to_owned()];
+        let error = <Day16 as Solution>::parse(&lines).unwrap_err();
+        assert!(error.to_string().contains("Message offset 5"));
+    }
+}
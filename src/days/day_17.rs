@@ -0,0 +1,324 @@
+use std::{collections::HashSet, fmt::Write};
+
+use itertools::Itertools;
+
+use crate::{intcode::*, *};
+
+type Position = (i32, i32);
+
+// Longest a movement routine can be, not counting its newline
+const MAX_ROUTINE: usize = 20;
+const FUNCTION_NAMES: [char; 3] = ['A', 'B', 'C'];
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    fn left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    fn right(self) -> Self {
+        self.left().left().left()
+    }
+
+    fn step(self, (x, y): Position) -> Position {
+        match self {
+            Direction::Up => (x, y - 1),
+            Direction::Right => (x + 1, y),
+            Direction::Down => (x, y + 1),
+            Direction::Left => (x - 1, y),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Move {
+    Left(usize),
+    Right(usize),
+}
+
+#[derive(Debug, Clone)]
+struct Camera {
+    image: String,
+    scaffold: HashSet<Position>,
+    robot: Position,
+    facing: Direction,
+}
+
+// The main routine as indexes into the movement functions
+#[derive(Debug, Clone, PartialEq)]
+struct Routines {
+    main: Vec<usize>,
+    functions: Vec<Vec<Move>>,
+}
+
+impl Camera {
+    fn read(image: &str) -> Self {
+        let mut scaffold = HashSet::new();
+        let mut robot = None;
+        for (y, row) in image.lines().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                let position = (x as i32, y as i32);
+                let facing = match c {
+                    '#' => None,
+                    '^' => Some(Direction::Up),
+                    '>' => Some(Direction::Right),
+                    'v' => Some(Direction::Down),
+                    '<' => Some(Direction::Left),
+                    _ => continue,
+                };
+                scaffold.insert(position);
+                if let Some(facing) = facing {
+                    robot = Some((position, facing));
+                }
+            }
+        }
+
+        let (robot, facing) = robot.expect("the camera didn't show the vacuum robot");
+        Camera {
+            image: image.trim_end().to_owned(),
+            scaffold,
+            robot,
+            facing,
+        }
+    }
+
+    fn intersections(&self) -> impl Iterator<Item = Position> + '_ {
+        let directions = [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ];
+        self.scaffold.iter().copied().filter(move |&position| {
+            directions
+                .iter()
+                .all(|direction| self.scaffold.contains(&direction.step(position)))
+        })
+    }
+
+    // Goes straight over every intersection, turning only at the corners
+    fn path(&self) -> Vec<Move> {
+        let (mut position, mut facing) = (self.robot, self.facing);
+        let mut moves = Vec::new();
+        loop {
+            let on_scaffold =
+                |direction: Direction| self.scaffold.contains(&direction.step(position));
+            let turn: fn(usize) -> Move = if on_scaffold(facing.left()) {
+                facing = facing.left();
+                Move::Left
+            } else if on_scaffold(facing.right()) {
+                facing = facing.right();
+                Move::Right
+            } else {
+                return moves;
+            };
+
+            let mut steps = 0;
+            while self.scaffold.contains(&facing.step(position)) {
+                position = facing.step(position);
+                steps += 1;
+            }
+            moves.push(turn(steps));
+        }
+    }
+}
+
+fn to_text(moves: &[Move]) -> String {
+    moves
+        .iter()
+        .map(|m| match m {
+            Move::Left(steps) => format!("L,{}", steps),
+            Move::Right(steps) => format!("R,{}", steps),
+        })
+        .join(",")
+}
+
+fn compress_from<'a>(
+    moves: &'a [Move],
+    functions: &mut Vec<&'a [Move]>,
+    main: &mut Vec<usize>,
+) -> bool {
+    if moves.is_empty() {
+        return true;
+    }
+    // Another call would make the main routine too long, at a name and a comma for each call
+    if (main.len() + 1) * 2 - 1 > MAX_ROUTINE {
+        return false;
+    }
+
+    for i in 0..functions.len() {
+        if moves.starts_with(functions[i]) {
+            main.push(i);
+            if compress_from(&moves[functions[i].len()..], functions, main) {
+                return true;
+            }
+            main.pop();
+        }
+    }
+
+    // Otherwise the next moves start a new function, as long as there's a name left for it
+    if functions.len() < FUNCTION_NAMES.len() {
+        for length in 1..=moves.len() {
+            if to_text(&moves[..length]).len() > MAX_ROUTINE {
+                break;
+            }
+            functions.push(&moves[..length]);
+            main.push(functions.len() - 1);
+            if compress_from(&moves[length..], functions, main) {
+                return true;
+            }
+            main.pop();
+            functions.pop();
+        }
+    }
+    false
+}
+
+// Splits the path into a main routine calling up to three functions, all short enough for the
+// robot's memory
+fn compress(moves: &[Move]) -> Option<Routines> {
+    let (mut functions, mut main) = (Vec::new(), Vec::new());
+    if !compress_from(moves, &mut functions, &mut main) {
+        return None;
+    }
+    Some(Routines {
+        main,
+        functions: functions.into_iter().map(<[Move]>::to_vec).collect(),
+    })
+}
+
+impl Routines {
+    // What the robot's asked for: the main routine, each function, and whether to show a video
+    // feed. The robot wants all three functions even if the main routine doesn't use them.
+    fn to_input(&self) -> String {
+        let mut input = self.main.iter().map(|&i| FUNCTION_NAMES[i]).join(",");
+        input.push('\n');
+        for i in 0..FUNCTION_NAMES.len() {
+            let function = self.functions.get(i).unwrap_or(&self.functions[0]);
+            writeln!(input, "{}", to_text(function)).unwrap();
+        }
+        input.push_str("n\n");
+        input
+    }
+}
+
+fn look(mem: &[i64]) -> Camera {
+    let transcript = ascii::run(mem, "").unwrap();
+    Camera::read(&transcript.text)
+}
+
+fn part01(camera: &Camera) -> i32 {
+    camera.intersections().map(|(x, y)| x * y).sum()
+}
+
+fn part02(mem: &[i64], camera: &Camera) -> i64 {
+    let routines = compress(&camera.path()).expect("couldn't fit the path into three functions");
+
+    // Wake the robot up so it takes movement routines
+    let mut mem = mem.to_vec();
+    mem[0] = 2;
+    let transcript = ascii::run(&mem, &routines.to_input()).unwrap();
+    *transcript
+        .values
+        .last()
+        .expect("the robot didn't report how much dust it collected")
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input = Vec<i64>;
+    type Part1 = i32;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Vec<i64>, ParseError> {
+        parse::int_list(parse::single_line(lines)?, ",")
+    }
+
+    fn part1(mem: &Vec<i64>) -> i32 {
+        part01(&look(mem))
+    }
+
+    fn part2(mem: &Vec<i64>) -> i64 {
+        part02(mem, &look(mem))
+    }
+
+    fn show(mem: &Vec<i64>) -> Option<String> {
+        let camera = look(mem);
+        let mut shown = format!("{}\n\n", camera.image);
+        if let Some(routines) = compress(&camera.path()) {
+            shown.push_str(&routines.to_input());
+        }
+        Some(shown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_alignment_parameters() {
+        let camera = Camera::read(
+            "..#..........\n\
+             ..#..........\n\
+             #######...###\n\
+             #.#...#...#.#\n\
+             #############\n\
+             ..#...#...#..\n\
+             ..#####...^..\n",
+        );
+        assert_eq!(part01(&camera), 76);
+    }
+
+    #[test]
+    fn compresses_the_path() {
+        let camera = Camera::read(
+            "#######...#####\n\
+             #.....#...#...#\n\
+             #.....#...#...#\n\
+             ......#...#...#\n\
+             ......#...###.#\n\
+             ......#.....#.#\n\
+             ^########...#.#\n\
+             ......#.#...#.#\n\
+             ......#########\n\
+             ........#...#..\n\
+             ....#########..\n\
+             ....#...#......\n\
+             ....#...#......\n\
+             ....#...#......\n\
+             ....#####......\n",
+        );
+        let path = camera.path();
+        assert_eq!(
+            to_text(&path),
+            "R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2"
+        );
+
+        let routines = compress(&path).unwrap();
+        let expanded = routines
+            .main
+            .iter()
+            .flat_map(|&i| routines.functions[i].iter().copied())
+            .collect::<Vec<_>>();
+        assert_eq!(expanded, path);
+        for line in routines.to_input().lines() {
+            assert!(line.len() <= MAX_ROUTINE, "{:?} is too long", line);
+        }
+    }
+}
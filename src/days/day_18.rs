@@ -0,0 +1,242 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+};
+
+use crate::*;
+
+type Position = (usize, usize);
+
+const KEYS: usize = 26;
+const MAX_ROBOTS: usize = 4;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Tile {
+    Wall,
+    Open,
+    Entrance,
+    // Numbered from 0 for a and A
+    Key(u8),
+    Door(u8),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vault {
+    tiles: Vec<Vec<Tile>>,
+}
+
+// The shortest way to a key, with the doors that need opening and the other keys picked up on
+// the way
+#[derive(Debug, Copy, Clone)]
+struct Route {
+    key: u8,
+    steps: usize,
+    doors: u32,
+    keys: u32,
+}
+
+// Where each robot is, as a key or entrance node, and which keys they've collected between them
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct State {
+    robots: [u8; MAX_ROBOTS],
+    keys: u32,
+}
+
+impl Vault {
+    fn find(&self, wanted: impl Fn(Tile) -> bool) -> Vec<(Position, Tile)> {
+        self.tiles
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, t)| ((x, y), *t)))
+            .filter(|(_, tile)| wanted(*tile))
+            .collect()
+    }
+
+    fn entrances(&self) -> Vec<Position> {
+        self.find(|tile| tile == Tile::Entrance)
+            .into_iter()
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    fn routes_from(&self, start: Position) -> Vec<Route> {
+        let mut routes = Vec::new();
+        let mut seen = vec![vec![false; self.tiles[0].len()]; self.tiles.len()];
+        let mut queue = VecDeque::new();
+        seen[start.1][start.0] = true;
+        queue.push_back((start, 0, 0, 0));
+
+        while let Some(((x, y), steps, mut doors, mut keys)) = queue.pop_front() {
+            match self.tiles[y][x] {
+                Tile::Key(key) if (x, y) != start => {
+                    routes.push(Route {
+                        key,
+                        steps,
+                        doors,
+                        keys,
+                    });
+                    keys |= 1 << key;
+                }
+                Tile::Door(door) => doors |= 1 << door,
+                _ => {}
+            }
+
+            // The edges of the vault are all wall, so the neighbours are always in the grid
+            for (nx, ny) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
+                if self.tiles[ny][nx] != Tile::Wall && !seen[ny][nx] {
+                    seen[ny][nx] = true;
+                    queue.push_back(((nx, ny), steps + 1, doors, keys));
+                }
+            }
+        }
+        routes
+    }
+
+    // Walls off the middle of the vault around its one entrance, leaving an entrance in each
+    // corner. Vaults that already have four entrances are left alone.
+    pub fn split(&self) -> Vault {
+        let entrances = self.entrances();
+        if entrances.len() != 1 {
+            return self.clone();
+        }
+
+        let (x, y) = entrances[0];
+        let mut tiles = self.tiles.clone();
+        for (dy, row) in ["@#@", "###", "@#@"].iter().enumerate() {
+            for (dx, c) in row.chars().enumerate() {
+                tiles[y + dy - 1][x + dx - 1] = match c {
+                    '@' => Tile::Entrance,
+                    _ => Tile::Wall,
+                };
+            }
+        }
+        Vault { tiles }
+    }
+
+    // Fewest steps for the robots to collect every key, moving one robot at a time from key to
+    // key, or None if some key can't be reached
+    pub fn collect_keys(&self) -> Option<usize> {
+        let entrances = self.entrances();
+        assert!(
+            !entrances.is_empty() && entrances.len() <= MAX_ROBOTS,
+            "expected 1 to {} entrances, found {}",
+            MAX_ROBOTS,
+            entrances.len()
+        );
+
+        // Keys are nodes 0 to 25, and the entrances come after them
+        let mut routes = vec![Vec::new(); KEYS + entrances.len()];
+        let mut all_keys = 0;
+        for (position, tile) in self.find(|tile| matches!(tile, Tile::Key(_))) {
+            if let Tile::Key(key) = tile {
+                routes[key as usize] = self.routes_from(position);
+                all_keys |= 1 << key;
+            }
+        }
+        let mut start = State {
+            robots: [0; MAX_ROBOTS],
+            keys: 0,
+        };
+        for (i, &entrance) in entrances.iter().enumerate() {
+            routes[KEYS + i] = self.routes_from(entrance);
+            start.robots[i] = (KEYS + i) as u8;
+        }
+
+        let mut best = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        best.insert(start, 0);
+        queue.push(Reverse((0, start)));
+
+        while let Some(Reverse((steps, state))) = queue.pop() {
+            if state.keys == all_keys {
+                return Some(steps);
+            }
+            if best.get(&state).is_some_and(|&b| b < steps) {
+                continue;
+            }
+
+            for robot in 0..entrances.len() {
+                let from = state.robots[robot] as usize;
+                for route in &routes[from] {
+                    let key = 1 << route.key;
+                    if state.keys & key != 0 || route.doors & !state.keys != 0 {
+                        continue;
+                    }
+
+                    let mut next = State {
+                        robots: state.robots,
+                        keys: state.keys | key | route.keys,
+                    };
+                    next.robots[robot] = route.key;
+                    let next_steps = steps + route.steps;
+                    if best.get(&next).is_none_or(|&b| next_steps < b) {
+                        best.insert(next, next_steps);
+                        queue.push(Reverse((next_steps, next)));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input = Vault;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Vault, ParseError> {
+        let tiles = parse::grid(lines, |c| match c {
+            '#' => Some(Tile::Wall),
+            '.' => Some(Tile::Open),
+            '@' => Some(Tile::Entrance),
+            'a'..='z' => Some(Tile::Key(c as u8 - b'a')),
+            'A'..='Z' => Some(Tile::Door(c as u8 - b'A')),
+            _ => None,
+        })?;
+
+        // Routes rely on the vault being walled in
+        for (y, row) in tiles.iter().enumerate() {
+            let edge = y == 0 || y == tiles.len() - 1;
+            for (x, tile) in row.iter().enumerate() {
+                if (edge || x == 0 || x == row.len() - 1) && *tile != Tile::Wall {
+                    return Err(ParseError {
+                        line: y + 1,
+                        column: x + 1,
+                        message: "expected the vault to be surrounded by walls".to_owned(),
+                    });
+                }
+            }
+        }
+
+        let vault = Vault { tiles };
+        let entrances = vault.entrances().len();
+        if entrances != 1 && entrances != MAX_ROBOTS {
+            return Err(parse::Span::new("", 1).error(format!(
+                "expected 1 or {} entrances, found {}",
+                MAX_ROBOTS, entrances
+            )));
+        }
+        Ok(vault)
+    }
+
+    fn part1(vault: &Vault) -> usize {
+        vault.collect_keys().expect("some keys can't be reached")
+    }
+
+    fn part2(vault: &Vault) -> usize {
+        vault
+            .split()
+            .collect_keys()
+            .expect("some keys can't be reached")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    crate::example_tests!(super::Day18);
+}
@@ -0,0 +1,166 @@
+use std::cell::Cell;
+
+use crate::{intcode::*, *};
+
+const AREA: usize = 50;
+const SHIP: usize = 100;
+// The beam's edges are lines from the emitter, so no row's beam starts further out than this
+// many times its y
+const MAX_SLOPE: usize = 10;
+
+// Asks a drone whether each point is in the beam, counting the drones sent. The beam is one
+// unbroken run of points in each row, with both ends only ever moving right, so the edges can be
+// followed down the rows without testing every point.
+struct Scanner<F: Fn(usize, usize) -> bool> {
+    pulls: F,
+    runs: Cell<usize>,
+}
+
+impl<F: Fn(usize, usize) -> bool> Scanner<F> {
+    fn new(pulls: F) -> Self {
+        Scanner {
+            pulls,
+            runs: Cell::new(0),
+        }
+    }
+
+    fn pulls(&self, x: usize, y: usize) -> bool {
+        self.runs.set(self.runs.get() + 1);
+        (self.pulls)(x, y)
+    }
+
+    // First x from `from` that's in the beam, or None if the row's beam doesn't start before
+    // `limit`. Rows near the emitter can miss the beam entirely.
+    fn left_edge(&self, y: usize, from: usize, limit: usize) -> Option<usize> {
+        (from..limit).find(|&x| self.pulls(x, y))
+    }
+
+    // Points in the beam within the square area next to the emitter
+    fn count(&self, size: usize) -> usize {
+        let (mut left, mut right) = (0, 0);
+        let mut count = 0;
+        for y in 0..size {
+            left = match self.left_edge(y, left, size) {
+                Some(x) => x,
+                None => continue,
+            };
+            right = right.max(left + 1);
+            while right < size && self.pulls(right, y) {
+                right += 1;
+            }
+            count += right - left;
+        }
+        count
+    }
+
+    // Top left corner of the square nearest the emitter that fits in the beam. Going down the
+    // rows, the first one where the square fits with its bottom left corner at the left edge has
+    // the square's top right corner in the beam too.
+    fn closest_square(&self, size: usize) -> (usize, usize) {
+        let mut left = 0;
+        for y in size - 1.. {
+            left = match self.left_edge(y, left, (y + 1) * MAX_SLOPE) {
+                Some(x) => x,
+                None => continue,
+            };
+            if self.pulls(left + size - 1, y + 1 - size) {
+                return (left, y + 1 - size);
+            }
+        }
+        unreachable!()
+    }
+}
+
+fn drone(mem: &[i64]) -> impl Fn(usize, usize) -> bool + '_ {
+    move |x, y| {
+        let (mut computer, sender, receiver) = IntcodeComputer::with_io(mem.to_vec());
+        sender.send(x as i64).unwrap();
+        sender.send(y as i64).unwrap();
+        computer.run();
+        receiver.recv().unwrap().1 == 1
+    }
+}
+
+fn part01(mem: &[i64]) -> usize {
+    Scanner::new(drone(mem)).count(AREA)
+}
+
+fn part02(mem: &[i64]) -> usize {
+    let (x, y) = Scanner::new(drone(mem)).closest_square(SHIP);
+    x * 10_000 + y
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Input = Vec<i64>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: &[String]) -> Result<Vec<i64>, ParseError> {
+        parse::int_list(parse::single_line(lines)?, ",")
+    }
+
+    fn part1(mem: &Vec<i64>) -> usize {
+        part01(mem)
+    }
+
+    fn part2(mem: &Vec<i64>) -> usize {
+        part02(mem)
+    }
+
+    // How many drones the edge tracking needed, against testing every point
+    fn show(mem: &Vec<i64>) -> Option<String> {
+        let scanner = Scanner::new(drone(mem));
+        scanner.count(AREA);
+        let count_runs = scanner.runs.replace(0);
+        let (x, y) = scanner.closest_square(SHIP);
+        Some(format!(
+            "Part 1 took {} Intcode runs, against {} for every point in the area\n\
+             Part 2 took {} Intcode runs, against {} for every point up to the square",
+            count_runs,
+            AREA * AREA,
+            scanner.runs.get(),
+            (x + SHIP) * (y + SHIP)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A beam between slopes of 3/4 and 6/5, with gaps near the emitter like the real one
+    fn beam(x: usize, y: usize) -> bool {
+        4 * x >= 3 * y && 5 * x <= 6 * y && (x, y) != (1, 1)
+    }
+
+    #[test]
+    fn follows_the_edges() {
+        let scanner = Scanner::new(beam);
+        let every_point = (0..AREA)
+            .flat_map(|y| (0..AREA).map(move |x| (x, y)))
+            .filter(|&(x, y)| beam(x, y))
+            .count();
+        assert_eq!(scanner.count(AREA), every_point);
+        assert!(scanner.runs.get() < AREA * AREA / 4);
+    }
+
+    #[test]
+    fn finds_the_closest_square() {
+        let size = 10;
+        let fits = |x: usize, y: usize| {
+            beam(x, y + size - 1)
+                && beam(x + size - 1, y)
+                && beam(x, y)
+                && beam(x + size - 1, y + size - 1)
+        };
+        let closest = (0..200)
+            .flat_map(|y| (0..200).map(move |x| (x, y)))
+            .find(|&(x, y)| fits(x, y))
+            .unwrap();
+        assert_eq!(Scanner::new(beam).closest_square(size), closest);
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::*;
+
+type Position = (usize, usize);
+
+const START: &str = "AA";
+const END: &str = "ZZ";
+// Deepest level the recursive search goes to, so a maze with no way out still finishes
+const MAX_DEPTH: usize = 200;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Maze {
+    open: HashSet<Position>,
+    // Where stepping into each portal leads, and whether the portal's on the outer edge
+    portals: HashMap<Position, (Position, bool)>,
+    start: Position,
+    end: Position,
+}
+
+impl Maze {
+    // Fewest steps from AA to ZZ. In the recursive maze inner portals lead a level down and outer
+    // ones a level up, the outer portals are walls on the outermost level, and ZZ only counts
+    // there.
+    pub fn shortest_path(&self, recursive: bool) -> Option<usize> {
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::new();
+        seen.insert((self.start, 0));
+        queue.push_back((self.start, 0, 0));
+
+        while let Some(((x, y), depth, steps)) = queue.pop_front() {
+            if (x, y) == self.end && depth == 0 {
+                return Some(steps);
+            }
+
+            let mut next = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ]
+            .iter()
+            .filter(|position| self.open.contains(position))
+            .map(|&position| (position, depth))
+            .collect::<Vec<_>>();
+            if let Some(&(to, outer)) = self.portals.get(&(x, y)) {
+                match (recursive, outer) {
+                    (false, _) => next.push((to, depth)),
+                    (true, true) if depth > 0 => next.push((to, depth - 1)),
+                    (true, false) if depth < MAX_DEPTH => next.push((to, depth + 1)),
+                    _ => {}
+                }
+            }
+
+            for state in next {
+                if seen.insert(state) {
+                    queue.push_back((state.0, state.1, steps + 1));
+                }
+            }
+        }
+        None
+    }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input = Maze;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    // Labels are two letters read left to right or top to bottom, next to the open square the
+    // portal leaves from. Rows can be ragged where trailing spaces have been trimmed.
+    fn parse(lines: &[String]) -> Result<Maze, ParseError> {
+        let mut grid = Vec::new();
+        for line in parse::lines(lines) {
+            let row = line.as_str().chars().collect::<Vec<_>>();
+            if let Some(i) = row
+                .iter()
+                .position(|&c| !matches!(c, '#' | '.' | ' ' | 'A'..='Z'))
+            {
+                return Err(ParseError {
+                    line: line.line,
+                    column: i + 1,
+                    message: format!("unexpected {:?}", row[i]),
+                });
+            }
+            grid.push(row);
+        }
+        let (height, width) = (grid.len(), grid.iter().map(Vec::len).max().unwrap_or(0));
+        let at = |x: usize, y: usize| grid[y].get(x).copied().unwrap_or(' ');
+
+        let mut open = HashSet::new();
+        let mut labels: HashMap<String, Vec<(Position, bool)>> = HashMap::new();
+        for y in 0..height {
+            for x in 0..width {
+                if at(x, y) != '.' {
+                    continue;
+                }
+                open.insert((x, y));
+
+                // Both letters of a label are on the same side, the far one still inside the grid
+                let sides = [
+                    (x >= 2).then(|| ((x - 2, y), (x - 1, y))),
+                    Some(((x + 1, y), (x + 2, y))).filter(|_| x + 2 < width),
+                    (y >= 2).then(|| ((x, y - 2), (x, y - 1))),
+                    Some(((x, y + 1), (x, y + 2))).filter(|_| y + 2 < height),
+                ];
+                for ((x1, y1), (x2, y2)) in sides.iter().flatten().copied() {
+                    let (first, second) = (at(x1, y1), at(x2, y2));
+                    if first.is_ascii_uppercase() && second.is_ascii_uppercase() {
+                        // Outer labels sit against the edge of the grid
+                        let outer = [x1, x2].contains(&0)
+                            || [y1, y2].contains(&0)
+                            || [x1, x2].contains(&(width - 1))
+                            || [y1, y2].contains(&(height - 1));
+                        labels
+                            .entry(format!("{}{}", first, second))
+                            .or_default()
+                            .push(((x, y), outer));
+                    }
+                }
+            }
+        }
+
+        let mut portals = HashMap::new();
+        let (mut start, mut end) = (None, None);
+        for (label, ends) in &labels {
+            match (label.as_str(), ends.as_slice()) {
+                (START, [(position, _)]) => start = Some(*position),
+                (END, [(position, _)]) => end = Some(*position),
+                (_, [(a, a_outer), (b, b_outer)]) if label != START && label != END => {
+                    portals.insert(*a, (*b, *a_outer));
+                    portals.insert(*b, (*a, *b_outer));
+                }
+                _ => {
+                    let ((x, y), _) = ends[0];
+                    return Err(ParseError {
+                        line: y + 1,
+                        column: x + 1,
+                        message: format!(
+                            "portal {} has {} ends next to open squares",
+                            label,
+                            ends.len()
+                        ),
+                    });
+                }
+            }
+        }
+
+        let missing = |label| parse::Span::new("", 1).error(format!("no {} portal", label));
+        Ok(Maze {
+            open,
+            portals,
+            start: start.ok_or_else(|| missing(START))?,
+            end: end.ok_or_else(|| missing(END))?,
+        })
+    }
+
+    fn part1(maze: &Maze) -> usize {
+        maze.shortest_path(false)
+            .expect("there's no way from AA to ZZ")
+    }
+
+    fn part2(maze: &Maze) -> usize {
+        maze.shortest_path(true).unwrap_or_else(|| {
+            panic!(
+                "there's no way from AA to ZZ within {} levels of the maze",
+                MAX_DEPTH
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    crate::example_tests!(super::Day20);
+}
@@ -0,0 +1,704 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt,
+    iter::Peekable,
+    str::CharIndices,
+};
+
+use crate::{intcode::*, *};
+
+// Springscript programs are boolean expressions over the droid's hull sensors, A being the
+// nearest square, compiled into the droid's instructions. The droid jumps whenever the expression
+// is true.
+
+const MAX_INSTRUCTIONS: usize = 15;
+const WALK_EXPRESSION: &str = "!(A & B & C) & D";
+const RUN_EXPRESSION: &str = "!(A & B & C) & D & (E | H)";
+// Most sensor readings the search puts in an expression
+const MAX_SEARCH_SIZE: usize = 7;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Mode {
+    Walk,
+    Run,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    // Numbered from 0 for A
+    Sensor(u8),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpringError {
+    Syntax { column: usize, reason: String },
+    // Needs more than the T and J registers to hold intermediate values
+    TooComplex,
+    TooLong(usize),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Register {
+    Sensor(u8),
+    T,
+    J,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Instruction(&'static str, Register, Register);
+
+// How a run of the droid went, with the hull it fell into if it didn't make it. Ground is true.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Damage(i64),
+    Fell(Vec<bool>),
+}
+
+// What the search came up with, and how much work it took
+#[derive(Debug, Clone, PartialEq)]
+pub struct Found {
+    pub expression: Expr,
+    pub damage: i64,
+    pub droid_runs: usize,
+    pub candidates: usize,
+}
+
+impl Mode {
+    fn sensors(self) -> u8 {
+        match self {
+            Mode::Walk => 4,
+            Mode::Run => 9,
+        }
+    }
+
+    fn command(self) -> &'static str {
+        match self {
+            Mode::Walk => "WALK",
+            Mode::Run => "RUN",
+        }
+    }
+}
+
+impl Register {
+    fn other(self) -> Self {
+        match self {
+            Register::T => Register::J,
+            _ => Register::T,
+        }
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    length: usize,
+    sensors: u8,
+}
+
+// Usual precedence, with ! binding tightest and | loosest
+impl Parser<'_> {
+    // The next character that isn't whitespace
+    fn peek(&mut self) -> Option<(usize, char)> {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        self.chars.peek().copied()
+    }
+
+    fn error(&mut self, reason: &str) -> SpringError {
+        let column = self.peek().map_or(self.length, |(i, _)| i) + 1;
+        SpringError::Syntax {
+            column,
+            reason: reason.to_owned(),
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, SpringError> {
+        let mut expr = self.and()?;
+        while let Some((_, '|')) = self.peek() {
+            self.chars.next();
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, SpringError> {
+        let mut expr = self.unary()?;
+        while let Some((_, '&')) = self.peek() {
+            self.chars.next();
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, SpringError> {
+        match self.peek() {
+            Some((_, '!')) => {
+                self.chars.next();
+                Ok(Expr::Not(Box::new(self.unary()?)))
+            }
+            Some((_, '(')) => {
+                self.chars.next();
+                let expr = self.or()?;
+                match self.peek() {
+                    Some((_, ')')) => {
+                        self.chars.next();
+                        Ok(expr)
+                    }
+                    _ => Err(self.error("expected )")),
+                }
+            }
+            Some((_, c @ 'A'..='I')) if (c as u8 - b'A') < self.sensors => {
+                self.chars.next();
+                Ok(Expr::Sensor(c as u8 - b'A'))
+            }
+            Some((_, c)) if c.is_ascii_uppercase() => Err(self.error(&format!(
+                "the droid only has sensors A to {}",
+                char::from(b'A' + self.sensors - 1)
+            ))),
+            _ => Err(self.error("expected a sensor, ! or (")),
+        }
+    }
+}
+
+impl Expr {
+    pub fn parse(text: &str, mode: Mode) -> Result<Expr, SpringError> {
+        let mut parser = Parser {
+            chars: text.char_indices().peekable(),
+            length: text.len(),
+            sensors: mode.sensors(),
+        };
+        let expr = parser.or()?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some(_) => Err(parser.error("expected & or |")),
+        }
+    }
+
+    // A sensor or its negation
+    fn literal(&self) -> Option<(u8, bool)> {
+        match self {
+            Expr::Sensor(s) => Some((*s, false)),
+            Expr::Not(inner) => match **inner {
+                Expr::Sensor(s) => Some((s, true)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn eval(&self, sensors: u16) -> bool {
+        match self {
+            Expr::Sensor(s) => sensors & (1 << s) != 0,
+            Expr::Not(e) => !e.eval(sensors),
+            Expr::And(a, b) => a.eval(sensors) && b.eval(sensors),
+            Expr::Or(a, b) => a.eval(sensors) || b.eval(sensors),
+        }
+    }
+}
+
+// Leaves the value of the expression in the register. The other register can only be used if
+// `scratch` says nothing's being kept in it.
+fn compile_into(
+    expr: &Expr,
+    register: Register,
+    scratch: bool,
+    out: &mut Vec<Instruction>,
+) -> Result<(), SpringError> {
+    let (op, dual, a, b) = match expr {
+        Expr::Sensor(s) => {
+            out.push(Instruction("NOT", Register::Sensor(*s), register));
+            out.push(Instruction("NOT", register, register));
+            return Ok(());
+        }
+        Expr::Not(inner) => {
+            match **inner {
+                Expr::Sensor(s) => out.push(Instruction("NOT", Register::Sensor(s), register)),
+                _ => {
+                    compile_into(inner, register, scratch, out)?;
+                    out.push(Instruction("NOT", register, register));
+                }
+            }
+            return Ok(());
+        }
+        Expr::And(a, b) => ("AND", "OR", a, b),
+        Expr::Or(a, b) => ("OR", "AND", a, b),
+    };
+
+    // Sensors can be read straight into an AND or OR, so put one on the right if there is one
+    let (a, b) = if b.literal().is_none() && a.literal().is_some() {
+        (b, a)
+    } else {
+        (a, b)
+    };
+    let other = register.other();
+    match b.literal() {
+        Some((s, false)) => {
+            compile_into(a, register, scratch, out)?;
+            out.push(Instruction(op, Register::Sensor(s), register));
+        }
+        Some((s, true)) if scratch => {
+            compile_into(a, register, scratch, out)?;
+            out.push(Instruction("NOT", Register::Sensor(s), other));
+            out.push(Instruction(op, other, register));
+        }
+        // a op !s is !(!a dual s)
+        Some((s, true)) => {
+            compile_into(a, register, scratch, out)?;
+            out.push(Instruction("NOT", register, register));
+            out.push(Instruction(dual, Register::Sensor(s), register));
+            out.push(Instruction("NOT", register, register));
+        }
+        None if scratch => {
+            compile_into(b, other, false, out)?;
+            compile_into(a, register, false, out)?;
+            out.push(Instruction(op, other, register));
+        }
+        None => return Err(SpringError::TooComplex),
+    }
+    Ok(())
+}
+
+// The springscript for the expression, ending with the command to start the droid
+pub fn compile(expr: &Expr, mode: Mode) -> Result<String, SpringError> {
+    let mut instructions = Vec::new();
+    compile_into(expr, Register::J, true, &mut instructions)?;
+    if instructions.len() > MAX_INSTRUCTIONS {
+        return Err(SpringError::TooLong(instructions.len()));
+    }
+
+    let mut program = String::new();
+    for instruction in instructions {
+        program.push_str(&format!("{}\n", instruction));
+    }
+    program.push_str(mode.command());
+    program.push('\n');
+    Ok(program)
+}
+
+pub fn survey(mem: &[i64], springscript: &str) -> Outcome {
+    let transcript = ascii::run(mem, springscript).unwrap();
+    if let Some(&damage) = transcript.values.last() {
+        return Outcome::Damage(damage);
+    }
+
+    // The hull's drawn under the droid in every frame of its last moments
+    let hull = transcript
+        .text
+        .lines()
+        .skip_while(|line| !line.starts_with("Didn't make it across"))
+        .find(|line| line.contains('#') && line.chars().all(|c| c == '#' || c == '.'))
+        .unwrap_or_else(|| panic!("the droid didn't say where it fell:\n{}", transcript.text));
+    Outcome::Fell(hull.chars().map(|c| c == '#').collect())
+}
+
+// What each sensor sees from a square, with the hull carrying on past where it was drawn
+fn sensors_at(hull: &[bool], x: usize, sensors: u8) -> u16 {
+    (0..sensors)
+        .filter(|&s| hull.get(x + 1 + s as usize).copied().unwrap_or(true))
+        .fold(0, |readings, s| readings | 1 << s)
+}
+
+fn crosses(hull: &[bool], sensors: u8, jumps: impl Fn(u16) -> bool) -> bool {
+    let mut x = 0;
+    while x < hull.len() {
+        if !hull[x] {
+            return false;
+        }
+        x += if jumps(sensors_at(hull, x, sensors)) {
+            4
+        } else {
+            1
+        };
+    }
+    true
+}
+
+// Candidates are built up from smaller ones, and only kept if they jump differently from every
+// smaller candidate somewhere on the known hulls
+#[derive(Debug, Copy, Clone)]
+enum Node {
+    Literal(u8, bool),
+    And((usize, usize), (usize, usize)),
+    Or((usize, usize), (usize, usize)),
+}
+
+struct Candidates {
+    // By size, then by index, with whether each one jumps for each of the situations
+    levels: Vec<Vec<(Node, Vec<bool>)>>,
+}
+
+impl Candidates {
+    fn expr(&self, (size, i): (usize, usize)) -> Expr {
+        match self.levels[size][i].0 {
+            Node::Literal(s, false) => Expr::Sensor(s),
+            Node::Literal(s, true) => Expr::Not(Box::new(Expr::Sensor(s))),
+            Node::And(a, b) => Expr::And(Box::new(self.expr(a)), Box::new(self.expr(b))),
+            Node::Or(a, b) => Expr::Or(Box::new(self.expr(a)), Box::new(self.expr(b))),
+        }
+    }
+}
+
+// The smallest expression, without any ! outside the sensors, that gets across all the hulls
+// and fits in the droid's memory
+fn synthesise(hulls: &[Vec<bool>], mode: Mode, tried: &mut usize) -> Option<Expr> {
+    let sensors = mode.sensors();
+    let mut situations = HashMap::new();
+    for hull in hulls {
+        for x in 0..hull.len() {
+            let count = situations.len();
+            situations
+                .entry(sensors_at(hull, x, sensors))
+                .or_insert(count);
+        }
+    }
+    let situations = situations;
+
+    let mut candidates = Candidates {
+        levels: vec![Vec::new()],
+    };
+    let mut seen = HashSet::new();
+    for size in 1..=MAX_SEARCH_SIZE {
+        let mut level = Vec::new();
+        if size == 1 {
+            for s in 0..sensors {
+                for negated in [false, true] {
+                    let mut jumps = vec![false; situations.len()];
+                    for (&readings, &i) in &situations {
+                        jumps[i] = (readings & (1 << s) != 0) != negated;
+                    }
+                    level.push((Node::Literal(s, negated), jumps));
+                }
+            }
+        } else {
+            for left in 1..=size / 2 {
+                let right = size - left;
+                for (i, (_, a)) in candidates.levels[left].iter().enumerate() {
+                    let start = if left == right { i } else { 0 };
+                    for (j, (_, b)) in candidates.levels[right].iter().enumerate().skip(start) {
+                        let and = a.iter().zip(b).map(|(x, y)| *x && *y).collect();
+                        let or = a.iter().zip(b).map(|(x, y)| *x || *y).collect();
+                        level.push((Node::And((left, i), (right, j)), and));
+                        level.push((Node::Or((left, i), (right, j)), or));
+                    }
+                }
+            }
+        }
+
+        level.retain(|(_, jumps)| seen.insert(jumps.clone()));
+        candidates.levels.push(level);
+        for i in 0..candidates.levels[size].len() {
+            *tried += 1;
+            let jumps = &candidates.levels[size][i].1;
+            if hulls
+                .iter()
+                .all(|hull| crosses(hull, sensors, |readings| jumps[situations[&readings]]))
+            {
+                let expr = candidates.expr((size, i));
+                if compile(&expr, mode).is_ok() {
+                    return Some(expr);
+                }
+            }
+        }
+    }
+    None
+}
+
+// Tries the smallest expression that gets across every hull the droid's fallen into so far,
+// learning a new hull each time it falls
+pub fn search(mem: &[i64], mode: Mode) -> Option<Found> {
+    let mut hulls: Vec<Vec<bool>> = Vec::new();
+    let mut candidates = 0;
+    loop {
+        let expression = synthesise(&hulls, mode, &mut candidates)?;
+        match survey(mem, &compile(&expression, mode).unwrap()) {
+            Outcome::Damage(damage) => {
+                return Some(Found {
+                    expression,
+                    damage,
+                    droid_runs: hulls.len() + 1,
+                    candidates,
+                })
+            }
+            Outcome::Fell(hull) => {
+                // Only possible if the droid and the search disagree about the rules
+                if !crosses(&hull, mode.sensors(), |readings| expression.eval(readings)) {
+                    hulls.push(hull);
+                } else {
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+// Compiles an expression and sends the droid out with it, returning the springscript too
+pub fn run_expression(
+    mem: &[i64],
+    expression: &str,
+    mode: Mode,
+) -> Result<(String, Outcome), SpringError> {
+    let program = compile(&Expr::parse(expression, mode)?, mode)?;
+    let outcome = survey(mem, &program);
+    Ok((program, outcome))
+}
+
+fn hull_damage(mem: &[i64], expression: &str, mode: Mode) -> i64 {
+    let (_, outcome) = run_expression(mem, expression, mode).unwrap_or_else(|e| panic!("{}", e));
+    match outcome {
+        Outcome::Damage(damage) => damage,
+        Outcome::Fell(_) => panic!("the springdroid fell into space running {}", expression),
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Input = Vec<i64>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: &[String]) -> Result<Vec<i64>, ParseError> {
+        parse::int_list(parse::single_line(lines)?, ",")
+    }
+
+    fn part1(mem: &Vec<i64>) -> i64 {
+        hull_damage(mem, WALK_EXPRESSION, Mode::Walk)
+    }
+
+    fn part2(mem: &Vec<i64>) -> i64 {
+        hull_damage(mem, RUN_EXPRESSION, Mode::Run)
+    }
+
+    // Searches for programs from scratch rather than using the ones written by hand
+    fn show(mem: &Vec<i64>) -> Option<String> {
+        let mut shown = String::new();
+        for mode in [Mode::Walk, Mode::Run] {
+            match search(mem, mode) {
+                Some(found) => shown.push_str(&format!(
+                    "{}: {} gives {} damage, after {} candidates and {} droid runs\n{}\n",
+                    mode.command(),
+                    found.expression,
+                    found.damage,
+                    found.candidates,
+                    found.droid_runs,
+                    compile(&found.expression, mode).unwrap()
+                )),
+                None => shown.push_str(&format!(
+                    "{}: nothing found with up to {} sensor readings\n\n",
+                    mode.command(),
+                    MAX_SEARCH_SIZE
+                )),
+            }
+        }
+        Some(shown)
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Register::Sensor(s) => write!(f, "{}", char::from(b'A' + s)),
+            Register::T => write!(f, "T"),
+            Register::J => write!(f, "J"),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.0, self.1, self.2)
+    }
+}
+
+// Brackets only where precedence needs them
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inside_and = |expr: &Expr| match expr {
+            Expr::Or(..) => format!("({})", expr),
+            _ => expr.to_string(),
+        };
+        match self {
+            Expr::Sensor(s) => write!(f, "{}", char::from(b'A' + s)),
+            Expr::Not(e) => match **e {
+                Expr::And(..) | Expr::Or(..) => write!(f, "!({})", e),
+                _ => write!(f, "!{}", e),
+            },
+            Expr::And(a, b) => write!(f, "{} & {}", inside_and(a), inside_and(b)),
+            Expr::Or(a, b) => write!(f, "{} | {}", a, b),
+        }
+    }
+}
+
+impl fmt::Display for SpringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax { column, reason } => write!(f, "Column {}: {}", column, reason),
+            Self::TooComplex => write!(f, "Expression needs more than the T and J registers"),
+            Self::TooLong(length) => write!(
+                f,
+                "Program is {} instructions long, but the droid only takes {}",
+                length, MAX_INSTRUCTIONS
+            ),
+        }
+    }
+}
+
+impl Error for SpringError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Runs springscript the way the droid does, for one set of sensor readings
+    fn jumps(program: &str, readings: u16) -> bool {
+        let (mut t, mut j) = (false, false);
+        for line in program.lines().filter(|line| line.contains(' ')) {
+            let words = line.split(' ').collect::<Vec<_>>();
+            let x = match words[1] {
+                "T" => t,
+                "J" => j,
+                sensor => readings & 1 << (sensor.as_bytes()[0] - b'A') != 0,
+            };
+            let y = if words[2] == "T" { &mut t } else { &mut j };
+            *y = match words[0] {
+                "AND" => x && *y,
+                "OR" => x || *y,
+                _ => !x,
+            };
+        }
+        j
+    }
+
+    #[test]
+    fn compiles_expressions() {
+        for text in [
+            WALK_EXPRESSION,
+            RUN_EXPRESSION,
+            "A",
+            "!A | !C & D",
+            "(A | !B) & (!C | D) & E",
+            "!(A | B & !C) | !D & !E",
+        ] {
+            let expr = Expr::parse(text, Mode::Run).unwrap();
+            let program = compile(&expr, Mode::Run).unwrap();
+            assert!(program.ends_with("RUN\n"));
+            for readings in 0..1 << 9 {
+                assert_eq!(jumps(&program, readings), expr.eval(readings), "{}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn reports_bad_expressions() {
+        let error = |text, mode| Expr::parse(text, mode).and_then(|e| compile(&e, mode));
+        assert!(matches!(
+            error("A & E", Mode::Walk),
+            Err(SpringError::Syntax { column: 5, .. })
+        ));
+        assert!(matches!(
+            error("(A | B", Mode::Walk),
+            Err(SpringError::Syntax { column: 7, .. })
+        ));
+        assert_eq!(
+            error("(A & B) | (C & D) | (E & F)", Mode::Run),
+            Err(SpringError::TooComplex)
+        );
+        assert_eq!(
+            error("!A & !B & !C & !D & !E & !F & !G & !H & !I", Mode::Run),
+            Err(SpringError::TooLong(17))
+        );
+    }
+
+    #[test]
+    fn prints_expressions() {
+        let expr = Expr::parse("!(A&B&C)&D&(E|H)", Mode::Run).unwrap();
+        assert_eq!(expr.to_string(), RUN_EXPRESSION);
+        assert_eq!(Expr::parse(&expr.to_string(), Mode::Run), Ok(expr));
+    }
+
+    fn hull(text: &str) -> Vec<bool> {
+        text.chars().map(|c| c == '#').collect()
+    }
+
+    #[test]
+    fn synthesises_expressions_for_known_hulls() {
+        let hulls = [
+            "#####.#..########",
+            "#####...#########",
+            "#####..#.########",
+        ]
+        .iter()
+        .map(|text| hull(text))
+        .collect::<Vec<_>>();
+        let walk = Expr::parse(WALK_EXPRESSION, Mode::Walk).unwrap();
+        assert!(hulls
+            .iter()
+            .all(|hull| crosses(hull, 4, |readings| walk.eval(readings))));
+
+        let mut tried = 0;
+        let found = synthesise(&hulls, Mode::Walk, &mut tried).unwrap();
+        assert!(hulls
+            .iter()
+            .all(|hull| crosses(hull, 4, |readings| found.eval(readings))));
+        assert!(compile(&found, Mode::Walk).is_ok());
+        assert!(tried > 0);
+
+        // With nothing learned yet, the first candidate that compiles will do
+        assert!(synthesise(&[], Mode::Walk, &mut tried).is_some());
+    }
+
+    // Needs the droid's program, which only comes with a cached day 21 input
+    fn droid_program() -> Option<Vec<i64>> {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/cache/2019/day_21_input");
+        let lines = std::fs::read_to_string(path)
+            .ok()?
+            .lines()
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        Some(<Day21 as Solution>::parse(&lines).unwrap())
+    }
+
+    #[test]
+    fn searches_with_the_droid() {
+        let mem = match droid_program() {
+            Some(mem) => mem,
+            None => return,
+        };
+        let found = search(&mem, Mode::Walk).unwrap();
+        assert_eq!(found.damage, hull_damage(&mem, WALK_EXPRESSION, Mode::Walk));
+        assert!(found.droid_runs <= found.candidates);
+    }
+
+    #[test]
+    fn runs_given_expressions() {
+        let mem = match droid_program() {
+            Some(mem) => mem,
+            None => return,
+        };
+
+        let (program, outcome) = run_expression(&mem, "D & (!A | !B | !C)", Mode::Walk).unwrap();
+        assert!(program.ends_with("WALK\n"));
+        assert_eq!(
+            outcome,
+            Outcome::Damage(hull_damage(&mem, WALK_EXPRESSION, Mode::Walk))
+        );
+
+        // Only jumping over holes right in front lands in the next one
+        let (_, outcome) = run_expression(&mem, "!A", Mode::Walk).unwrap();
+        assert!(matches!(outcome, Outcome::Fell(_)));
+
+        assert!(run_expression(&mem, "A & E", Mode::Walk).is_err());
+    }
+
+    #[test]
+    fn follows_the_droid_over_the_hull() {
+        let hull = hull("#####.##.####");
+        let walk = Expr::parse(WALK_EXPRESSION, Mode::Walk).unwrap();
+        assert!(crosses(&hull, 4, |readings| walk.eval(readings)));
+        assert!(!crosses(&hull, 4, |readings| readings & 1 == 0));
+    }
+}
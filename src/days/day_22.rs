@@ -0,0 +1,206 @@
+use crate::*;
+
+const SMALL_DECK: u64 = 10_007;
+const LARGE_DECK: u64 = 119_315_717_514_047;
+const SHUFFLES: u64 = 101_741_582_076_661;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Technique {
+    NewStack,
+    Cut(i64),
+    Increment(u64),
+}
+
+// Where a shuffle moves the card at position x: a * x + b, modulo the deck size. Products are
+// taken in 128 bits, which can't overflow for decks of up to 2^64 cards.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Affine {
+    a: u64,
+    b: u64,
+    deck: u64,
+}
+
+impl Affine {
+    fn identity(deck: u64) -> Self {
+        Affine { a: 1, b: 0, deck }
+    }
+
+    fn from_technique(technique: Technique, deck: u64) -> Self {
+        let (a, b) = match technique {
+            Technique::NewStack => (deck - 1, deck - 1),
+            Technique::Cut(n) => (1, (-(n as i128)).rem_euclid(deck as i128) as u64),
+            Technique::Increment(n) => (n % deck, 0),
+        };
+        Affine { a, b, deck }
+    }
+
+    fn mul(&self, x: u64, y: u64) -> u64 {
+        (x as u128 * y as u128 % self.deck as u128) as u64
+    }
+
+    fn add(&self, x: u64, y: u64) -> u64 {
+        ((x as u128 + y as u128) % self.deck as u128) as u64
+    }
+
+    pub fn apply(&self, x: u64) -> u64 {
+        self.add(self.mul(self.a, x), self.b)
+    }
+
+    // This shuffle followed by the next one
+    pub fn then(&self, next: &Affine) -> Affine {
+        Affine {
+            a: self.mul(next.a, self.a),
+            b: self.add(self.mul(next.a, self.b), next.b),
+            deck: self.deck,
+        }
+    }
+
+    // The shuffle done n times, by repeated squaring
+    pub fn pow(&self, mut n: u64) -> Affine {
+        let (mut result, mut square) = (Affine::identity(self.deck), *self);
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.then(&square);
+            }
+            square = square.then(&square);
+            n >>= 1;
+        }
+        result
+    }
+
+    // Where each card came from, which only exists if a is coprime with the deck size
+    pub fn inverse(&self) -> Option<Affine> {
+        let a = mod_inverse(self.a, self.deck)?;
+        Some(Affine {
+            a,
+            b: self.mul(a, self.deck - self.b % self.deck),
+            deck: self.deck,
+        })
+    }
+}
+
+// By the extended Euclidean algorithm
+fn mod_inverse(x: u64, m: u64) -> Option<u64> {
+    let (mut old_r, mut r) = (x as i128, m as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+    (old_r == 1).then(|| old_s.rem_euclid(m as i128) as u64)
+}
+
+fn shuffle(techniques: &[Technique], deck: u64) -> Affine {
+    techniques
+        .iter()
+        .fold(Affine::identity(deck), |shuffle, &technique| {
+            shuffle.then(&Affine::from_technique(technique, deck))
+        })
+}
+
+fn part01(techniques: &[Technique]) -> u64 {
+    shuffle(techniques, SMALL_DECK).apply(2019)
+}
+
+fn part02(techniques: &[Technique]) -> u64 {
+    shuffle(techniques, LARGE_DECK)
+        .pow(SHUFFLES)
+        .inverse()
+        .expect("the shuffle can't be undone, as an increment shares a factor with the deck size")
+        .apply(2020)
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    const ITERATIONS: usize = 100;
+
+    type Input = Vec<Technique>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(lines: &[String]) -> Result<Vec<Technique>, ParseError> {
+        parse::lines(lines)
+            .map(|line| {
+                if line.as_str() == "deal into new stack" {
+                    Ok(Technique::NewStack)
+                } else if let Ok(n) = line.strip_prefix("cut ") {
+                    Ok(Technique::Cut(n.parse()?))
+                } else if let Ok(n) = line.strip_prefix("deal with increment ") {
+                    match n.parse()? {
+                        0 => Err(n.error("expected an increment of at least 1")),
+                        n => Ok(Technique::Increment(n)),
+                    }
+                } else {
+                    Err(line.error(format!("unknown technique {:?}", line.as_str())))
+                }
+            })
+            .collect()
+    }
+
+    fn part1(techniques: &Vec<Technique>) -> u64 {
+        part01(techniques)
+    }
+
+    fn part2(techniques: &Vec<Technique>) -> u64 {
+        part02(techniques)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The cards in the order they end up, from the top
+    fn deal(techniques: &str) -> Vec<u64> {
+        let lines = techniques.lines().map(str::to_owned).collect::<Vec<_>>();
+        let techniques = <Day22 as Solution>::parse(&lines).unwrap();
+        let from = shuffle(&techniques, 10).inverse().unwrap();
+        (0..10).map(|position| from.apply(position)).collect()
+    }
+
+    #[test]
+    fn shuffles_small_decks() {
+        assert_eq!(
+            deal("deal with increment 7\ndeal into new stack\ndeal into new stack"),
+            vec![0, 3, 6, 9, 2, 5, 8, 1, 4, 7]
+        );
+        assert_eq!(
+            deal("cut 6\ndeal with increment 7\ndeal into new stack"),
+            vec![3, 0, 7, 4, 1, 8, 5, 2, 9, 6]
+        );
+        assert_eq!(
+            deal("deal with increment 7\ndeal with increment 9\ncut -2"),
+            vec![6, 3, 0, 7, 4, 1, 8, 5, 2, 9]
+        );
+        assert_eq!(
+            deal(
+                "deal into new stack\ncut -2\ndeal with increment 7\ncut 8\ncut -4\n\
+                 deal with increment 7\ncut 3\ndeal with increment 9\ndeal with increment 3\n\
+                 cut -1"
+            ),
+            vec![9, 2, 5, 8, 1, 4, 7, 0, 3, 6]
+        );
+    }
+
+    #[test]
+    fn repeats_and_undoes_shuffles() {
+        let techniques = [
+            Technique::Cut(-3),
+            Technique::Increment(7),
+            Technique::NewStack,
+        ];
+        let once = shuffle(&techniques, LARGE_DECK);
+        let repeated = (0..5).fold(Affine::identity(LARGE_DECK), |s, _| s.then(&once));
+        assert_eq!(once.pow(5), repeated);
+        assert_eq!(
+            repeated.inverse().unwrap().apply(repeated.apply(2020)),
+            2020
+        );
+
+        // An increment that isn't coprime with the deck can't be undone
+        assert_eq!(shuffle(&[Technique::Increment(5)], 10).inverse(), None);
+    }
+}
@@ -0,0 +1,216 @@
+use std::{collections::HashSet, fmt};
+
+use crate::*;
+
+const SIZE: usize = 5;
+const CELLS: usize = SIZE * SIZE;
+const CENTRE: usize = CELLS / 2;
+const MINUTES: usize = 200;
+
+// One bit per cell, reading along the rows from the top left, which makes the biodiversity
+// rating the bits themselves
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Eris(u32);
+
+// The cells next to each cell, as masks over the grid outside it, the same grid, and the grid
+// inside it. The flat grid only uses the middle mask, and leaves its centre in.
+#[derive(Debug, Copy, Clone, Default)]
+struct Neighbours {
+    outer: u32,
+    same: u32,
+    inner: u32,
+}
+
+fn neighbours(recursive: bool) -> [Neighbours; CELLS] {
+    let mut all = [Neighbours::default(); CELLS];
+    let bit = |x: usize, y: usize| 1u32 << (y * SIZE + x);
+    let edge = |side: (isize, isize)| -> u32 {
+        (0..SIZE)
+            .map(|i| match side {
+                (-1, 0) => bit(0, i),
+                (1, 0) => bit(SIZE - 1, i),
+                (0, -1) => bit(i, 0),
+                _ => bit(i, SIZE - 1),
+            })
+            .sum()
+    };
+
+    for (cell, neighbours) in all.iter_mut().enumerate() {
+        let (x, y) = ((cell % SIZE) as isize, (cell / SIZE) as isize);
+        for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+            let (nx, ny) = (x + dx, y + dy);
+            let centre = (SIZE / 2) as isize;
+            if !(0..SIZE as isize).contains(&nx) || !(0..SIZE as isize).contains(&ny) {
+                if recursive {
+                    neighbours.outer |= bit((centre + dx) as usize, (centre + dy) as usize);
+                }
+            } else if recursive && (nx, ny) == (centre, centre) {
+                // The side of the inner grid facing this cell
+                neighbours.inner |= edge((-dx, -dy));
+            } else {
+                neighbours.same |= bit(nx as usize, ny as usize);
+            }
+        }
+    }
+    all
+}
+
+// A bug survives next to exactly one bug, and an empty cell is infested next to one or two
+fn next_cell(bug: bool, adjacent: u32) -> bool {
+    adjacent == 1 || (!bug && adjacent == 2)
+}
+
+impl Eris {
+    fn bug(&self, cell: usize) -> bool {
+        self.0 & 1 << cell != 0
+    }
+
+    fn step(&self, neighbours: &[Neighbours; CELLS]) -> Eris {
+        Eris(
+            (0..CELLS)
+                .filter(|&cell| {
+                    next_cell(
+                        self.bug(cell),
+                        (self.0 & neighbours[cell].same).count_ones(),
+                    )
+                })
+                .map(|cell| 1 << cell)
+                .sum(),
+        )
+    }
+
+    // The first layout to appear twice
+    pub fn first_repeat(&self) -> Eris {
+        let neighbours = neighbours(false);
+        let mut seen = HashSet::new();
+        let mut eris = *self;
+        while seen.insert(eris) {
+            eris = eris.step(&neighbours);
+        }
+        eris
+    }
+
+    pub fn biodiversity(&self) -> u32 {
+        self.0
+    }
+
+    // Bugs after the given minutes, with this grid's centre holding a grid of its own and so on
+    // down, and this grid sitting in the centre of a bigger one and so on up. Levels are added
+    // as bugs spread to them, one each way at most per minute.
+    pub fn recursive_bugs(&self, minutes: usize) -> u32 {
+        let neighbours = neighbours(true);
+        // Outermost first
+        let mut levels = vec![self.0 & !(1 << CENTRE)];
+        for _ in 0..minutes {
+            if levels[0] != 0 {
+                levels.insert(0, 0);
+            }
+            if levels[levels.len() - 1] != 0 {
+                levels.push(0);
+            }
+
+            levels = (0..levels.len())
+                .map(|i| {
+                    let outer = if i > 0 { levels[i - 1] } else { 0 };
+                    let inner = levels.get(i + 1).copied().unwrap_or(0);
+                    (0..CELLS)
+                        .filter(|&cell| cell != CENTRE)
+                        .filter(|&cell| {
+                            let n = &neighbours[cell];
+                            let adjacent = (outer & n.outer).count_ones()
+                                + (levels[i] & n.same).count_ones()
+                                + (inner & n.inner).count_ones();
+                            next_cell(levels[i] & 1 << cell != 0, adjacent)
+                        })
+                        .map(|cell| 1 << cell)
+                        .sum()
+                })
+                .collect();
+        }
+        levels.iter().map(|level| level.count_ones()).sum()
+    }
+}
+
+impl fmt::Display for Eris {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..SIZE {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..SIZE {
+                write!(f, "{}", if self.bug(y * SIZE + x) { '#' } else { '.' })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    const ITERATIONS: usize = 100;
+
+    type Input = Eris;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    // The centre can be drawn as ? for the recursive grid, which counts as empty
+    fn parse(lines: &[String]) -> Result<Eris, ParseError> {
+        let rows = parse::grid(lines, |c| match c {
+            '#' => Some(true),
+            '.' | '?' => Some(false),
+            _ => None,
+        })?;
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.len() != SIZE || width != SIZE {
+            return Err(parse::Span::new("", 1).error(format!(
+                "expected a {} by {} grid, found {} by {}",
+                SIZE,
+                SIZE,
+                width,
+                rows.len()
+            )));
+        }
+
+        Ok(Eris(
+            rows.iter()
+                .flatten()
+                .enumerate()
+                .filter(|(_, &bug)| bug)
+                .map(|(cell, _)| 1 << cell)
+                .sum(),
+        ))
+    }
+
+    fn part1(eris: &Eris) -> u32 {
+        eris.first_repeat().biodiversity()
+    }
+
+    fn part2(eris: &Eris) -> u32 {
+        eris.recursive_bugs(MINUTES)
+    }
+
+    // The layout that repeats
+    fn show(eris: &Eris) -> Option<String> {
+        Some(eris.first_repeat().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!(super::Day24, part1);
+
+    // The example only gives the recursive bugs after 10 minutes
+    #[test]
+    fn spreads_through_levels() {
+        let lines = ["....#", "#..#.", "#.?##", "..#..", "#...."]
+            .iter()
+            .map(|&line| line.to_owned())
+            .collect::<Vec<_>>();
+        let eris = <Day24 as Solution>::parse(&lines).unwrap();
+        assert_eq!(eris.recursive_bugs(10), 99);
+    }
+}
@@ -0,0 +1,471 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    error::Error,
+    fmt,
+};
+
+use crate::{
+    intcode::{ascii::Session, *},
+    *,
+};
+
+// Instructions a command can take before the program's taken to be stuck in a loop. Ordinary
+// commands take well under a tenth of this.
+pub const MAX_INSTRUCTIONS: usize = 1_000_000;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Direction::North => "north",
+            Direction::South => "south",
+            Direction::East => "east",
+            Direction::West => "west",
+        }
+    }
+
+    fn reverse(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Room {
+    pub name: String,
+    pub description: String,
+    pub doors: Vec<Direction>,
+    // What was lying here when the droid first arrived
+    pub items: Vec<String>,
+}
+
+// What taking an item does, for the items that aren't safe to carry
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Hazard {
+    Fatal,
+    Loops,
+    Stuck,
+}
+
+// The ship as the droid mapped it, and how it got past the pressure-sensitive floor
+#[derive(Debug, Clone, Default)]
+pub struct Expedition {
+    pub rooms: BTreeMap<String, Room>,
+    pub doors: HashMap<(String, Direction), String>,
+    pub hazards: BTreeMap<String, Hazard>,
+    pub items: Vec<String>,
+    pub carried: Vec<String>,
+    pub password: String,
+    // Everything the game said, with the droid's commands after each prompt
+    pub transcript: String,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Section {
+    Description,
+    Doors,
+    Items,
+    Other,
+}
+
+// Every room described in the text, in order. Being sent back from the floor describes the floor
+// and then the room the droid ends up in.
+fn rooms(text: &str) -> Vec<Room> {
+    let mut rooms: Vec<Room> = Vec::new();
+    let mut section = Section::Other;
+    for line in text.lines() {
+        if let Some(name) = line.strip_prefix("== ").and_then(|l| l.strip_suffix(" ==")) {
+            rooms.push(Room {
+                name: name.to_owned(),
+                ..Room::default()
+            });
+            section = Section::Description;
+            continue;
+        }
+        let room = match rooms.last_mut() {
+            Some(room) => room,
+            None => continue,
+        };
+
+        match (line, line.strip_prefix("- "), section) {
+            ("", _, _) => section = Section::Other,
+            ("Doors here lead:", _, _) => section = Section::Doors,
+            ("Items here:", _, _) => section = Section::Items,
+            (_, Some(door), Section::Doors) => {
+                room.doors
+                    .extend(Direction::ALL.iter().find(|d| d.name() == door));
+            }
+            (_, Some(item), Section::Items) => room.items.push(item.to_owned()),
+            (_, _, Section::Description) => {
+                if !room.description.is_empty() {
+                    room.description.push('\n');
+                }
+                room.description.push_str(line);
+            }
+            _ => {}
+        }
+    }
+    rooms
+}
+
+// Santa says to type it on the keypad
+fn password(text: &str) -> Option<String> {
+    let (_, rest) = text.split_once("typing ")?;
+    let digits = rest
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>();
+    (!digits.is_empty()).then_some(digits)
+}
+
+struct Explorer<'a> {
+    mem: &'a [i64],
+    session: Session,
+    // Commands given so far, to replay when trying out items
+    history: Vec<String>,
+    here: String,
+    // The checkpoint and its door to the floor
+    floor: Option<(String, Direction)>,
+    expedition: Expedition,
+}
+
+impl<'a> Explorer<'a> {
+    fn new(mem: &'a [i64]) -> Result<Self, Box<dyn Error>> {
+        let mut session = Session::new(mem, MAX_INSTRUCTIONS);
+        let (transcript, _) = session.send("")?;
+        let start = rooms(&transcript.text)
+            .pop()
+            .ok_or_else(|| format!("Expected a room to start in:\n{}", transcript.text))?;
+
+        let mut expedition = Expedition {
+            transcript: transcript.text,
+            ..Expedition::default()
+        };
+        let here = start.name.clone();
+        expedition.rooms.insert(start.name.clone(), start);
+        Ok(Explorer {
+            mem,
+            session,
+            history: Vec::new(),
+            here,
+            floor: None,
+            expedition,
+        })
+    }
+
+    fn send(&mut self, command: &str) -> Result<(String, Status), Box<dyn Error>> {
+        let (transcript, status) = self.session.send(&format!("{}\n", command))?;
+        self.history.push(command.to_owned());
+        let log = &mut self.expedition.transcript;
+        log.push_str(command);
+        log.push('\n');
+        log.push_str(&transcript.text);
+        Ok((transcript.text, status))
+    }
+
+    // Sends a command the game should carry on after
+    fn command(&mut self, command: &str) -> Result<String, Box<dyn Error>> {
+        match self.send(command)? {
+            (text, Status::WantsInput) => Ok(text),
+            (text, status) => {
+                Err(format!("{:?} after {:?}, which said:\n{}", status, command, text).into())
+            }
+        }
+    }
+
+    fn go(&mut self, door: Direction) -> Result<Vec<Room>, Box<dyn Error>> {
+        let text = self.command(door.name())?;
+        let rooms = rooms(&text);
+        match rooms.last() {
+            Some(room) => self.here = room.name.clone(),
+            None => {
+                return Err(
+                    format!("Couldn't go {} from {}:\n{}", door.name(), self.here, text).into(),
+                )
+            }
+        }
+        Ok(rooms)
+    }
+
+    // Tries taking the item in a replay of the game so far, since some items end the game, never
+    // give control back, or stop the droid moving
+    fn hazard(&self, item: &str, room: &Room) -> Result<Option<Hazard>, Box<dyn Error>> {
+        let mut session = Session::new(self.mem, MAX_INSTRUCTIONS);
+        session.send("")?;
+        for command in &self.history {
+            session.send(&format!("{}\n", command))?;
+        }
+
+        match session.send(&format!("take {}\n", item))? {
+            (_, Status::Halted) => return Ok(Some(Hazard::Fatal)),
+            (_, Status::Running) => return Ok(Some(Hazard::Loops)),
+            (_, Status::WantsInput) => {}
+        }
+        let door = match room.doors.first() {
+            Some(door) => door,
+            None => return Ok(None),
+        };
+        let (transcript, status) = session.send(&format!("{}\n", door.name()))?;
+        let moved = status == Status::WantsInput && !rooms(&transcript.text).is_empty();
+        Ok((!moved).then_some(Hazard::Stuck))
+    }
+
+    // Takes the safe items here, then goes through each door not yet taken and back, mapping
+    // the rooms beyond it on the way. Going onto the floor sends the droid back, so it's noted
+    // and left for later.
+    fn map(&mut self) -> Result<(), Box<dyn Error>> {
+        let here = self.here.clone();
+        let room = self.expedition.rooms[&here].clone();
+        for item in &room.items {
+            match self.hazard(item, &room)? {
+                Some(hazard) => {
+                    self.expedition.hazards.insert(item.clone(), hazard);
+                }
+                None => {
+                    self.command(&format!("take {}", item))?;
+                    self.expedition.items.push(item.clone());
+                }
+            }
+        }
+
+        for &door in &room.doors {
+            if self.expedition.doors.contains_key(&(here.clone(), door)) {
+                continue;
+            }
+
+            let rooms = self.go(door)?;
+            let there = rooms[0].name.clone();
+            let new = !self.expedition.rooms.contains_key(&there);
+            for room in rooms {
+                self.expedition
+                    .rooms
+                    .entry(room.name.clone())
+                    .or_insert(room);
+            }
+            self.expedition
+                .doors
+                .insert((here.clone(), door), there.clone());
+            self.expedition
+                .doors
+                .insert((there.clone(), door.reverse()), here.clone());
+            if self.here != there {
+                self.floor = Some((here.clone(), door));
+                continue;
+            }
+
+            if new {
+                self.map()?;
+            }
+            self.go(door.reverse())?;
+        }
+        Ok(())
+    }
+
+    // Doors to take from here to the room, not counting the one onto the floor
+    fn path(&self, to: &str) -> Option<Vec<Direction>> {
+        let mut came_by: HashMap<&str, (&str, Direction)> = HashMap::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(self.here.as_str());
+
+        while let Some(room) = queue.pop_front() {
+            if room == to {
+                let mut path = Vec::new();
+                let mut at = room;
+                while let Some(&(from, door)) = came_by.get(at) {
+                    path.push(door);
+                    at = from;
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for door in Direction::ALL {
+                let key = (room.to_owned(), door);
+                if self.floor.as_ref() == Some(&key) {
+                    continue;
+                }
+                if let Some(next) = self.expedition.doors.get(&key) {
+                    if next != &self.here && !came_by.contains_key(next.as_str()) {
+                        came_by.insert(next, (room, door));
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    // Goes to the checkpoint and steps onto the floor with each set of items until one weighs
+    // right. Trying the sets in Gray code order only changes one item between tries.
+    fn pass_floor(&mut self) -> Result<(), Box<dyn Error>> {
+        let (checkpoint, door) = self
+            .floor
+            .clone()
+            .ok_or("Never found the pressure-sensitive floor")?;
+        let path = self
+            .path(&checkpoint)
+            .ok_or_else(|| format!("No way back to {}", checkpoint))?;
+        for step in path {
+            self.go(step)?;
+        }
+
+        let items = self.expedition.items.clone();
+        for item in &items {
+            self.command(&format!("drop {}", item))?;
+        }
+        let mut held = 0u32;
+        for i in 0..1u32 << items.len() {
+            let next = i ^ (i >> 1);
+            let changed = held ^ next;
+            if changed != 0 {
+                let verb = if next & changed != 0 { "take" } else { "drop" };
+                let item = &items[changed.trailing_zeros() as usize];
+                self.command(&format!("{} {}", verb, item))?;
+            }
+            held = next;
+
+            let (text, _) = self.send(door.name())?;
+            if let Some(password) = password(&text) {
+                self.expedition.password = password;
+                self.expedition.carried = (0..items.len())
+                    .filter(|i| held & 1 << i != 0)
+                    .map(|i| items[i].clone())
+                    .collect();
+                return Ok(());
+            }
+        }
+        Err("No set of items got the droid past the floor".into())
+    }
+}
+
+// Maps the whole ship, collecting every item that's safe to carry, then works out which of them
+// get the droid past the floor to Santa
+pub fn explore(mem: &[i64]) -> Result<Expedition, Box<dyn Error>> {
+    let mut explorer = Explorer::new(mem)?;
+    explorer.map()?;
+    explorer.pass_floor()?;
+    Ok(explorer.expedition)
+}
+
+impl fmt::Display for Hazard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Hazard::Fatal => write!(f, "ends the game"),
+            Hazard::Loops => write!(f, "never gives control back"),
+            Hazard::Stuck => write!(f, "stops the droid moving"),
+        }
+    }
+}
+
+// Each room with where its doors lead and what was in it, then the items left behind
+impl fmt::Display for Expedition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, room) in &self.rooms {
+            let doors = room
+                .doors
+                .iter()
+                .map(|&door| {
+                    let to = self.doors.get(&(name.clone(), door));
+                    format!("{} to {}", door.name(), to.map_or("?", String::as_str))
+                })
+                .collect::<Vec<_>>();
+            write!(f, "{}: {}", name, doors.join(", "))?;
+            if !room.items.is_empty() {
+                write!(f, " (items: {})", room.items.join(", "))?;
+            }
+            writeln!(f)?;
+        }
+        for (item, hazard) in &self.hazards {
+            writeln!(f, "Left the {}, which {}", item, hazard)?;
+        }
+        write!(f, "Got past the floor with {}", self.carried.join(", "))
+    }
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+
+    type Input = Expedition;
+    type Part1 = String;
+    type Part2 = &'static str;
+
+    // The whole expedition happens here, so it's only done once for the password and the map
+    fn parse(lines: &[String]) -> Result<Expedition, ParseError> {
+        let mem = parse::int_list(parse::single_line(lines)?, ",")?;
+        explore(&mem).map_err(|e| {
+            parse::Span::new("", 1).error(format!("the droid didn't reach Santa: {}", e))
+        })
+    }
+
+    fn part1(expedition: &Expedition) -> String {
+        expedition.password.clone()
+    }
+
+    // There's no second puzzle on the last day
+    fn part2(_expedition: &Expedition) -> &'static str {
+        NO_ANSWER
+    }
+
+    fn show(expedition: &Expedition) -> Option<String> {
+        Some(expedition.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_rooms() {
+        let text = "\n\n\n== Security Checkpoint ==\nIn the next room, a pressure-sensitive \
+                    floor will verify your identity.\n\nDoors here lead:\n- north\n- west\n\n\
+                    Items here:\n- mutex\n- giant electromagnet\n\nCommand?\n";
+        assert_eq!(
+            rooms(text),
+            vec![Room {
+                name: "Security Checkpoint".to_owned(),
+                description: "In the next room, a pressure-sensitive floor will verify your \
+                              identity."
+                    .to_owned(),
+                doors: vec![Direction::North, Direction::West],
+                items: vec!["mutex".to_owned(), "giant electromagnet".to_owned()],
+            }]
+        );
+
+        let ejected = "\n\n\n== Pressure-Sensitive Floor ==\nAnalyzing...\n\nDoors here lead:\n\
+                       - south\n\nA loud, robotic voice says \"Alert! Droids on this ship are \
+                       heavier than the detected value!\" and you are ejected back to the \
+                       checkpoint.\n\n\n\n== Security Checkpoint ==\nIn the next room.\n\n\
+                       Doors here lead:\n- north\n\nCommand?\n";
+        let names = rooms(ejected).into_iter().map(|room| room.name);
+        assert!(names.eq(["Pressure-Sensitive Floor", "Security Checkpoint"]));
+    }
+
+    #[test]
+    fn finds_the_password() {
+        let text = "\"Oh, hello! You should be able to get in by typing 1234567 on the keypad \
+                    at the main airlock.\"\n";
+        assert_eq!(password(text), Some("1234567".to_owned()));
+        assert_eq!(password("You aren't carrying any items."), None);
+    }
+}
@@ -0,0 +1,214 @@
+use std::{fmt, fs, io, path::PathBuf};
+
+use crate::AnySolution;
+
+// The examples from the puzzle descriptions, kept as `src/examples/<year>/day_NN` files next to
+// the input cache. Each example starts with a header giving the answers it should produce, and
+// the lines up to the next header are its input:
+//
+//     # Anything before the first header is a comment
+//     === part1=654 part2=966
+//     1969
+//
+// Either answer can be left out, for examples that only apply to one part.
+
+pub const EXAMPLES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/examples");
+
+const HEADER: &str = "===";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    // 1-based line of the example's header
+    pub line: usize,
+    pub input: Vec<String>,
+    pub answers: [Option<String>; 2],
+}
+
+#[derive(Debug)]
+pub enum ExampleError {
+    Io(PathBuf, io::Error),
+    Malformed { line: usize, reason: String },
+}
+
+pub fn path(year: u16, day: u8) -> PathBuf {
+    PathBuf::from(EXAMPLES_DIR)
+        .join(year.to_string())
+        .join(format!("day_{:02}", day))
+}
+
+pub fn parse(contents: &str) -> Result<Vec<Example>, ExampleError> {
+    let malformed = |line, reason: &str| ExampleError::Malformed {
+        line,
+        reason: reason.to_owned(),
+    };
+
+    let mut examples: Vec<Example> = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let header = match line.strip_prefix(HEADER) {
+            Some(header) => header,
+            None => {
+                if let Some(example) = examples.last_mut() {
+                    example.input.push(line.to_owned());
+                }
+                continue;
+            }
+        };
+
+        let mut answers = [None, None];
+        for field in header.split_whitespace() {
+            let (part, answer) = match field.split_once('=') {
+                Some(("part1", answer)) => (0, answer),
+                Some(("part2", answer)) => (1, answer),
+                _ => return Err(malformed(i + 1, &format!("unknown field {:?}", field))),
+            };
+            answers[part] = Some(answer.to_owned());
+        }
+        if answers == [None, None] {
+            return Err(malformed(i + 1, "example has no answers"));
+        }
+        examples.push(Example {
+            line: i + 1,
+            input: Vec::new(),
+            answers,
+        });
+    }
+
+    for example in &mut examples {
+        while example
+            .input
+            .last()
+            .is_some_and(|line| line.trim().is_empty())
+        {
+            example.input.pop();
+        }
+        if example.input.is_empty() {
+            return Err(malformed(example.line, "example has no input"));
+        }
+    }
+    Ok(examples)
+}
+
+pub fn load(year: u16, day: u8) -> Result<Vec<Example>, ExampleError> {
+    let path = path(year, day);
+    let contents = fs::read_to_string(&path).map_err(|e| ExampleError::Io(path, e))?;
+    parse(&contents)
+}
+
+// Runs a part of the solution on every example with an answer for it, panicking with a list of
+// the ones that went wrong
+pub fn check(solution: &dyn AnySolution, part: usize) {
+    let (year, day) = (solution.year(), solution.day());
+    let examples = load(year, day).unwrap_or_else(|e| panic!("{}", e));
+
+    let mut checked = 0;
+    let mut failures = Vec::new();
+    for example in &examples {
+        let expected = match &example.answers[part - 1] {
+            Some(expected) => expected,
+            None => continue,
+        };
+        let input = match solution.parse(&example.input) {
+            Ok(input) => input,
+            Err(e) => {
+                failures.push(format!("example at line {}: {}", example.line, e));
+                checked += 1;
+                continue;
+            }
+        };
+        let answer = match part {
+            1 => solution.part1(&*input),
+            _ => solution.part2(&*input),
+        };
+        if answer != *expected {
+            failures.push(format!(
+                "example at line {}: got {}, expected {}",
+                example.line, answer, expected
+            ));
+        }
+        checked += 1;
+    }
+
+    assert!(
+        checked > 0,
+        "{} has no examples for part {}",
+        path(year, day).display(),
+        part
+    );
+    assert!(
+        failures.is_empty(),
+        "Day {:02} part {}:\n{}",
+        day,
+        part,
+        failures.join("\n")
+    );
+}
+
+// Adds a test for each part that checks the day's examples, or just the parts given, e.g. in a
+// day's test module:
+//
+//     example_tests!(super::Day01);
+//     example_tests!(super::Day12, part2);
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:expr) => {
+        $crate::example_tests!($solution, part1, part2);
+    };
+    ($solution:expr, $($part:ident),+) => {
+        $($crate::example_tests!(@part $solution, $part);)+
+    };
+    (@part $solution:expr, part1) => {
+        #[test]
+        fn part1_examples() {
+            $crate::examples::check(&$solution, 1);
+        }
+    };
+    (@part $solution:expr, part2) => {
+        #[test]
+        fn part2_examples() {
+            $crate::examples::check(&$solution, 2);
+        }
+    };
+}
+
+impl fmt::Display for ExampleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(f, "Couldn't read {}: {}", path.display(), e),
+            Self::Malformed { line, reason } => {
+                write!(f, "Malformed examples at line {}: {}", line, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExampleError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_examples() {
+        let examples = parse(
+            "# Comment\n=== part1=2\n12\n\n=== part1=654 part2=966\n1969\n=== part2=A\n#.#\n .#\n",
+        )
+        .unwrap();
+        assert_eq!(examples.len(), 3);
+        assert_eq!(examples[0].line, 2);
+        assert_eq!(examples[0].input, vec!["12"]);
+        assert_eq!(examples[0].answers, [Some("2".to_owned()), None]);
+        assert_eq!(
+            examples[1].answers,
+            [Some("654".to_owned()), Some("966".to_owned())]
+        );
+        // Grid rows and leading spaces are input, not comments
+        assert_eq!(examples[2].input, vec!["#.#", " .#"]);
+    }
+
+    #[test]
+    fn rejects_malformed_examples() {
+        assert!(parse("=== part3=1\n12\n").is_err());
+        assert!(parse("=== \n12\n").is_err());
+        assert!(parse("=== part1=1\n\n=== part1=2\n12\n").is_err());
+    }
+}
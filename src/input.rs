@@ -0,0 +1,653 @@
+pub mod check;
+
+use std::{
+    collections::BTreeMap,
+    env,
+    error::Error,
+    fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use self::check::Problem;
+use crate::client::{Client, FetchError};
+
+// Used when AOC_CACHE_DIR isn't set
+pub const DEFAULT_CACHE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/cache");
+
+// Kept in each event's cache directory, as `<file> <checksum>` per line
+const CHECKSUMS: &str = "checksums";
+
+// The year the solutions in this crate are for, unless they say otherwise
+pub const YEAR: u16 = 2019;
+
+#[derive(Debug)]
+pub enum InputError {
+    Io(PathBuf, io::Error),
+    NotCached {
+        event: Event,
+        day: u8,
+        cache_dir: PathBuf,
+    },
+    Fetch(u8, FetchError),
+    // Named events have no inputs on the server, only what's been put in their cache
+    NotFetchable(Event, u8),
+    Empty(String),
+    Invalid(String, Problem),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CacheStatus {
+    Ok,
+    // Looks right, but there's no checksum from when it was fetched
+    Unverified,
+    // Looks right, but isn't what was fetched
+    Modified,
+    Invalid(Problem),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedInput {
+    pub day: u8,
+    pub path: PathBuf,
+    pub size: u64,
+    pub status: CacheStatus,
+}
+
+// A year of puzzles. The name tells apart events that share a year, e.g. ones fetched from a
+// different server, and keeps their inputs in a separate part of the cache.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Event {
+    pub year: u16,
+    pub name: Option<String>,
+}
+
+// Where a day's input comes from
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    // The cache directory, fetching and caching the input if it's missing
+    Cache,
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    // `-` means stdin, anything else is a file path
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn read(&self, event: &Event, day: u8) -> Result<Vec<String>, InputError> {
+        let (name, contents) = match self {
+            InputSource::Cache => return get_event_input(event, day),
+            InputSource::File(path) => (
+                path.display().to_string(),
+                fs::read_to_string(path).map_err(|e| InputError::Io(path.clone(), e))?,
+            ),
+            InputSource::Stdin => {
+                let mut contents = String::new();
+                io::stdin()
+                    .read_to_string(&mut contents)
+                    .map_err(|e| InputError::Io(PathBuf::from("-"), e))?;
+                ("stdin".to_owned(), contents)
+            }
+        };
+
+        to_lines(&name, &contents)
+    }
+}
+
+// An Intcode program named on the command line: a path to a program file or, if there's no such
+// file, a day whose input is the program
+pub fn load_program(program: &str) -> Result<Vec<String>, InputError> {
+    let path = Path::new(program);
+    match program.parse::<u8>() {
+        Ok(day) if !path.exists() => get_input(day),
+        _ => {
+            let contents =
+                fs::read_to_string(path).map_err(|e| InputError::Io(path.to_owned(), e))?;
+            to_lines(&path.display().to_string(), &contents)
+        }
+    }
+}
+
+impl Event {
+    pub fn year(year: u16) -> Self {
+        Event { year, name: None }
+    }
+
+    pub fn named(name: &str, year: u16) -> Self {
+        Event {
+            year,
+            name: Some(name.to_owned()),
+        }
+    }
+
+    // Where the event's inputs are kept under the cache root, i.e. `[name/]year`
+    pub fn cache_dir(&self, root: &Path) -> PathBuf {
+        match &self.name {
+            Some(name) => root.join(name).join(self.year.to_string()),
+            None => root.join(self.year.to_string()),
+        }
+    }
+
+    // Where the server keeps the input, which it only has for the year's own event
+    fn input_path(&self, day: u8) -> Option<String> {
+        match self.name {
+            Some(_) => None,
+            None => Some(format!("/{}/day/{}/input", self.year, day)),
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{} {}", name, self.year),
+            None => write!(f, "{}", self.year),
+        }
+    }
+}
+
+// The cache root, holding a directory per event
+pub fn cache_dir() -> PathBuf {
+    env::var_os("AOC_CACHE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CACHE_DIR))
+}
+
+fn to_lines(name: &str, contents: &str) -> Result<Vec<String>, InputError> {
+    if contents.trim().is_empty() {
+        return Err(InputError::Empty(name.to_owned()));
+    }
+    Ok(contents.lines().map(str::to_owned).collect())
+}
+
+// Reads a file from the cache, populating it from the factory if it's missing. Nothing is
+// cached if the factory fails.
+fn from_cache<T: FnOnce() -> Result<Vec<String>, InputError>>(
+    cache_dir: &Path,
+    name: &str,
+    factory: T,
+) -> Result<Vec<String>, InputError> {
+    let file_cache_path = cache_dir.join(name);
+
+    match fs::read_to_string(&file_cache_path) {
+        Ok(contents) => to_lines(&file_cache_path.display().to_string(), &contents),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            let result = factory()?;
+
+            // Populate cache
+            fs::create_dir_all(cache_dir).map_err(|e| InputError::Io(cache_dir.to_owned(), e))?;
+            write_cached(cache_dir, name, &result.join("\n"))?;
+            Ok(result)
+        }
+        Err(error) => Err(InputError::Io(file_cache_path, error)),
+    }
+}
+
+fn read_checksums(cache_dir: &Path) -> Result<BTreeMap<String, String>, InputError> {
+    let path = cache_dir.join(CHECKSUMS);
+    match fs::read_to_string(&path) {
+        Ok(contents) => Ok(contents
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .map(|(name, sum)| (name.to_owned(), sum.to_owned()))
+            .collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(InputError::Io(path, e)),
+    }
+}
+
+fn write_checksums(
+    cache_dir: &Path,
+    checksums: &BTreeMap<String, String>,
+) -> Result<(), InputError> {
+    let path = cache_dir.join(CHECKSUMS);
+    let contents: String = checksums
+        .iter()
+        .map(|(name, sum)| format!("{} {}\n", name, sum))
+        .collect();
+    fs::write(&path, contents).map_err(|e| InputError::Io(path, e))
+}
+
+// Writes a file into the cache along with its checksum
+fn write_cached(cache_dir: &Path, name: &str, contents: &str) -> Result<(), InputError> {
+    let path = cache_dir.join(name);
+    fs::create_dir_all(cache_dir).map_err(|e| InputError::Io(cache_dir.to_owned(), e))?;
+    fs::write(&path, contents).map_err(|e| InputError::Io(path, e))?;
+
+    let mut checksums = read_checksums(cache_dir)?;
+    checksums.insert(name.to_owned(), check::checksum(contents));
+    write_checksums(cache_dir, &checksums)
+}
+
+fn input_name(day: u8) -> String {
+    format!("day_{:02}_input", day)
+}
+
+// The day a cache file is the input for, if it is one
+fn input_day(name: &str) -> Option<u8> {
+    name.strip_prefix("day_")?
+        .strip_suffix("_input")?
+        .parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+}
+
+// Every input cached for the event, with whether it's intact
+pub fn cached_inputs(root: &Path, event: &Event) -> Result<Vec<CachedInput>, InputError> {
+    let event_dir = event.cache_dir(root);
+    let entries = match fs::read_dir(&event_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(InputError::Io(event_dir, e)),
+    };
+    let checksums = read_checksums(&event_dir)?;
+
+    let mut inputs = Vec::new();
+    for entry in entries.filter_map(Result::ok) {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let day = match input_day(&name) {
+            Some(day) => day,
+            None => continue,
+        };
+
+        let path = entry.path();
+        let contents = fs::read_to_string(&path).map_err(|e| InputError::Io(path.clone(), e))?;
+        let status = match check::validate(event.year, day, &contents) {
+            Err(problem) => CacheStatus::Invalid(problem),
+            Ok(()) => match checksums.get(&name) {
+                None => CacheStatus::Unverified,
+                Some(sum) if *sum == check::checksum(&contents) => CacheStatus::Ok,
+                Some(_) => CacheStatus::Modified,
+            },
+        };
+        inputs.push(CachedInput {
+            day,
+            size: contents.len() as u64,
+            path,
+            status,
+        });
+    }
+    inputs.sort_by_key(|input| input.day);
+    Ok(inputs)
+}
+
+// Fetches the input again, replacing whatever's cached once the new copy checks out
+pub fn refetch_input(root: &Path, event: &Event, day: u8) -> Result<Vec<String>, InputError> {
+    let event_dir = event.cache_dir(root);
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(FetchError::NoSession) => {
+            return Err(InputError::NotCached {
+                event: event.clone(),
+                day,
+                cache_dir: event_dir,
+            })
+        }
+        Err(e) => return Err(InputError::Fetch(day, e)),
+    };
+
+    let lines = fetch_checked_input(&client, event, day)?;
+    write_cached(&event_dir, &input_name(day), &lines.join("\n"))?;
+    Ok(lines)
+}
+
+// Records checksums for inputs that pass their checks but don't have one, e.g. ones cached
+// before there were checksums. Returns the days that were added.
+pub fn trust_cached_inputs(root: &Path, event: &Event) -> Result<Vec<u8>, InputError> {
+    let event_dir = event.cache_dir(root);
+    let mut checksums = read_checksums(&event_dir)?;
+
+    let mut trusted = Vec::new();
+    for input in cached_inputs(root, event)? {
+        if input.status == CacheStatus::Unverified {
+            let contents = fs::read_to_string(&input.path)
+                .map_err(|e| InputError::Io(input.path.clone(), e))?;
+            checksums.insert(input_name(input.day), check::checksum(&contents));
+            trusted.push(input.day);
+        }
+    }
+    if !trusted.is_empty() {
+        write_checksums(&event_dir, &checksums)?;
+    }
+    Ok(trusted)
+}
+
+// Removes inputs that are invalid or modified, anything else that isn't an input, and
+// checksums for files that have gone. Returns the paths that were removed.
+pub fn prune_cache(root: &Path, event: &Event) -> Result<Vec<PathBuf>, InputError> {
+    let event_dir = event.cache_dir(root);
+    let entries = match fs::read_dir(&event_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(InputError::Io(event_dir, e)),
+    };
+
+    let corrupted = cached_inputs(root, event)?
+        .into_iter()
+        .filter(|input| {
+            matches!(
+                input.status,
+                CacheStatus::Modified | CacheStatus::Invalid(_)
+            )
+        })
+        .map(|input| input.path)
+        .collect::<Vec<_>>();
+    let mut removed = Vec::new();
+    for entry in entries.filter_map(Result::ok) {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let path = entry.path();
+        let stray = name != CHECKSUMS && input_day(&name).is_none();
+        // Directories may hold named events, so are left alone
+        if path.is_file() && (stray || corrupted.contains(&path)) {
+            fs::remove_file(&path).map_err(|e| InputError::Io(path.clone(), e))?;
+            removed.push(path);
+        }
+    }
+
+    let mut checksums = read_checksums(&event_dir)?;
+    let before = checksums.len();
+    checksums.retain(|name, _| event_dir.join(name).is_file());
+    if checksums.len() != before {
+        write_checksums(&event_dir, &checksums)?;
+    }
+    Ok(removed)
+}
+
+// 2019's inputs used to be cached straight into the cache root, before there were
+// directories per event. Moves any that are still there, returning how many were moved.
+pub fn migrate_flat_cache(root: &Path) -> Result<usize, InputError> {
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(InputError::Io(root.to_owned(), e)),
+    };
+
+    let event_dir = Event::year(2019).cache_dir(root);
+    let mut moved = 0;
+    for entry in entries.filter_map(Result::ok) {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let is_input = name.starts_with("day_") && name.ends_with("_input");
+        if !is_input || !entry.path().is_file() || event_dir.join(&name).exists() {
+            continue;
+        }
+
+        fs::create_dir_all(&event_dir).map_err(|e| InputError::Io(event_dir.clone(), e))?;
+        fs::rename(entry.path(), event_dir.join(&name))
+            .map_err(|e| InputError::Io(entry.path(), e))?;
+        moved += 1;
+    }
+    Ok(moved)
+}
+
+fn fetch_input(client: &Client, event: &Event, day: u8) -> Result<Vec<String>, InputError> {
+    let path = event
+        .input_path(day)
+        .ok_or_else(|| InputError::NotFetchable(event.clone(), day))?;
+    let body = client
+        .get_text(&path)
+        .map_err(|e| InputError::Fetch(day, e))?;
+    to_lines(&format!("the server for day {}", day), &body)
+}
+
+// Fetches an input, refusing anything that doesn't look like the day's input
+fn fetch_checked_input(client: &Client, event: &Event, day: u8) -> Result<Vec<String>, InputError> {
+    let lines = fetch_input(client, event, day)?;
+    check::validate(event.year, day, &lines.join("\n"))
+        .map_err(|problem| InputError::Invalid(format!("the server for day {}", day), problem))?;
+    Ok(lines)
+}
+
+pub fn get_event_input(event: &Event, day: u8) -> Result<Vec<String>, InputError> {
+    let root = cache_dir();
+    let event_dir = event.cache_dir(&root);
+    let name = input_name(day);
+
+    if *event == Event::year(2019) && !event_dir.join(&name).exists() {
+        migrate_flat_cache(&root)?;
+    }
+
+    from_cache(&event_dir, &name, || match Client::from_env() {
+        Ok(client) => fetch_checked_input(&client, event, day),
+        Err(FetchError::NoSession) => Err(InputError::NotCached {
+            event: event.clone(),
+            day,
+            cache_dir: event_dir.clone(),
+        }),
+        Err(e) => Err(InputError::Fetch(day, e)),
+    })
+}
+
+pub fn get_input(day: u8) -> Result<Vec<String>, InputError> {
+    get_event_input(&Event::year(YEAR), day)
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(f, "Couldn't read {}: {}", path.display(), e),
+            Self::NotCached {
+                event,
+                day,
+                cache_dir,
+            } => write!(
+                f,
+                "No input for day {} of {} in {}, and AOC_SESSION isn't set to fetch it",
+                day,
+                event,
+                cache_dir.display()
+            ),
+            Self::Fetch(day, e) => write!(f, "Couldn't fetch input for day {}: {}", day, e),
+            Self::NotFetchable(event, day) => write!(
+                f,
+                "Day {} of {} isn't cached, and only a year's own inputs can be fetched",
+                day, event
+            ),
+            Self::Empty(name) => write!(f, "Input from {} is empty", name),
+            Self::Invalid(name, problem) => {
+                write!(f, "Input from {} is invalid: {}", name, problem)
+            }
+        }
+    }
+}
+
+impl Error for InputError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::{mock_server, response};
+
+    fn temp_cache(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc2019_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn caches_fetched_input() {
+        let (server, handle) = mock_server(vec![response("200 OK", "12\n14\n1969\n")]);
+        let client = Client::new(server, "abc123");
+        let dir = temp_cache("fetched");
+
+        let lines = from_cache(&dir, "day_01_input", || {
+            fetch_input(&client, &Event::year(2019), 1)
+        })
+        .unwrap();
+        assert_eq!(lines, vec!["12", "14", "1969"]);
+        handle.join().unwrap();
+
+        // The second read comes from the cache, so never reaches the factory
+        let cached = from_cache(&dir, "day_01_input", || panic!("should be cached")).unwrap();
+        assert_eq!(cached, lines);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn doesnt_cache_failed_fetches() {
+        let (server, handle) = mock_server(vec![
+            response(
+                "200 OK",
+                "<!DOCTYPE html><a href=\"/auth/login\">[Log In]</a>",
+            ),
+            response("404 Not Found", "404 Not Found"),
+        ]);
+        let client = Client::new(server, "expired");
+        let dir = temp_cache("failed");
+
+        let result = from_cache(&dir, "day_01_input", || {
+            fetch_input(&client, &Event::year(2019), 1)
+        });
+        assert!(matches!(
+            result,
+            Err(InputError::Fetch(1, FetchError::NotLoggedIn))
+        ));
+        let result = from_cache(&dir, "day_30_input", || {
+            fetch_input(&client, &Event::year(2019), 30)
+        });
+        assert!(matches!(
+            result,
+            Err(InputError::Fetch(30, FetchError::Status { code: 404, .. }))
+        ));
+        // A named event never reaches the server
+        let result = from_cache(&dir, "day_01_input", || {
+            fetch_input(&client, &Event::named("mirror", 2019), 1)
+        });
+        assert!(matches!(result, Err(InputError::NotFetchable(_, 1))));
+        handle.join().unwrap();
+
+        assert!(!dir.join("day_01_input").exists());
+        assert!(!dir.join("day_30_input").exists());
+    }
+
+    #[test]
+    fn rejects_misshapen_fetches() {
+        let (server, handle) = mock_server(vec![response("200 OK", "1,0,0,3,1,1,2,")]);
+        let client = Client::new(server, "abc123");
+        let dir = temp_cache("misshapen");
+
+        let result = from_cache(&dir, "day_02_input", || {
+            fetch_checked_input(&client, &Event::year(2019), 2)
+        });
+        assert!(matches!(result, Err(InputError::Invalid(_, _))));
+        handle.join().unwrap();
+        assert!(!dir.join("day_02_input").exists());
+    }
+
+    #[test]
+    fn checks_and_prunes_cache() {
+        let root = temp_cache("manage");
+        let event = Event::year(2019);
+        let dir = event.cache_dir(&root);
+        write_cached(&dir, "day_02_input", "1,0,0,3,99").unwrap();
+        write_cached(&dir, "day_05_input", "3,0,4,0,99").unwrap();
+        fs::write(dir.join("day_05_input"), "3,0,4,0,98").unwrap();
+        fs::write(dir.join("day_10_input"), ".#..#\n.#").unwrap();
+        fs::write(
+            dir.join("day_24_input"),
+            "....#\n#..#.\n#..##\n..#..\n#....",
+        )
+        .unwrap();
+        fs::write(dir.join("day_02_input.tmp"), "").unwrap();
+
+        let statuses = |root| {
+            cached_inputs(root, &event)
+                .unwrap()
+                .into_iter()
+                .map(|input| (input.day, input.status))
+                .collect::<Vec<_>>()
+        };
+        let invalid = check::validate(2019, 10, ".#..#\n.#").unwrap_err();
+        assert_eq!(
+            statuses(&root),
+            vec![
+                (2, CacheStatus::Ok),
+                (5, CacheStatus::Modified),
+                (10, CacheStatus::Invalid(invalid)),
+                (24, CacheStatus::Unverified)
+            ]
+        );
+
+        assert_eq!(trust_cached_inputs(&root, &event).unwrap(), vec![24]);
+        let mut removed = prune_cache(&root, &event).unwrap();
+        removed.sort();
+        assert_eq!(
+            removed,
+            vec![
+                dir.join("day_02_input.tmp"),
+                dir.join("day_05_input"),
+                dir.join("day_10_input")
+            ]
+        );
+        assert_eq!(
+            statuses(&root),
+            vec![(2, CacheStatus::Ok), (24, CacheStatus::Ok)]
+        );
+        assert_eq!(read_checksums(&dir).unwrap().len(), 2);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn namespaces_events() {
+        let root = Path::new("cache");
+        assert_eq!(Event::year(2019).cache_dir(root), root.join("2019"));
+        assert_eq!(
+            Event::named("mirror", 2020).cache_dir(root),
+            root.join("mirror").join("2020")
+        );
+        assert_eq!(
+            Event::year(2015).input_path(7).as_deref(),
+            Some("/2015/day/7/input")
+        );
+        assert_eq!(Event::named("mirror", 2015).input_path(7), None);
+    }
+
+    #[test]
+    fn loads_programs_from_files() {
+        let dir = temp_cache("program");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("program");
+        fs::write(&path, "1,0,0,0,\n99\n").unwrap();
+
+        let program = path.to_str().unwrap();
+        assert_eq!(load_program(program).unwrap(), vec!["1,0,0,0,", "99"]);
+        assert!(matches!(
+            load_program(&format!("{}.missing", program)),
+            Err(InputError::Io(_, _))
+        ));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn migrates_flat_cache() {
+        let root = temp_cache("flat");
+        fs::create_dir_all(root.join("2019")).unwrap();
+        fs::write(root.join("day_01_input"), "12").unwrap();
+        fs::write(root.join("day_02_input"), "old").unwrap();
+        fs::write(root.join("2019").join("day_02_input"), "new").unwrap();
+        fs::write(root.join("notes"), "").unwrap();
+
+        assert_eq!(migrate_flat_cache(&root).unwrap(), 1);
+        assert_eq!(
+            fs::read_to_string(root.join("2019/day_01_input")).unwrap(),
+            "12"
+        );
+        assert!(!root.join("day_01_input").exists());
+
+        // Anything already migrated wins over a stale flat copy
+        assert_eq!(
+            fs::read_to_string(root.join("2019/day_02_input")).unwrap(),
+            "new"
+        );
+        assert!(root.join("day_02_input").exists());
+        assert!(root.join("notes").exists());
+
+        assert_eq!(migrate_flat_cache(&root).unwrap(), 0);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
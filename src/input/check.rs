@@ -0,0 +1,210 @@
+use std::fmt;
+
+use regex::Regex;
+
+// Sanity checks for cached inputs, so a truncated download or an error page saved in place of
+// an input is caught before a solution trips over it
+
+// What a day's input should look like
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shape {
+    // A single line of comma-separated integers
+    Intcode,
+    // Every line matches the pattern, optionally with a fixed number of lines
+    Lines {
+        pattern: &'static str,
+        count: Option<usize>,
+    },
+    // Equal-length rows of cells matching a character class, optionally of a fixed size
+    Grid {
+        cells: &'static str,
+        size: Option<(usize, usize)>,
+    },
+    // Nothing's known about it beyond not being empty
+    Any,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    // 1-based, if the problem is with a particular line
+    pub line: Option<usize>,
+    pub reason: String,
+}
+
+pub fn shape(year: u16, day: u8) -> Shape {
+    let lines = |pattern| Shape::Lines {
+        pattern,
+        count: None,
+    };
+    let line = |pattern, count| Shape::Lines {
+        pattern,
+        count: Some(count),
+    };
+
+    if year != 2019 {
+        return Shape::Any;
+    }
+    match day {
+        2 | 5 | 7 | 9 | 11 | 13 | 15 | 17 | 19 | 21 | 23 | 25 => Shape::Intcode,
+        1 => lines(r"\d+"),
+        3 => line(r"[UDLR]\d+(,[UDLR]\d+)*", 2),
+        4 => line(r"\d+-\d+", 1),
+        6 => lines(r"[A-Z0-9]+\)[A-Z0-9]+"),
+        // Layers of 25x6 pixels
+        8 => line(r"(\d{150})+", 1),
+        10 => Shape::Grid {
+            cells: "[.#]",
+            size: None,
+        },
+        12 => lines(r"<x=-?\d+, y=-?\d+, z=-?\d+>"),
+        14 => lines(r"\d+ [A-Z]+(, \d+ [A-Z]+)* => \d+ [A-Z]+"),
+        16 => line(r"\d+", 1),
+        18 => Shape::Grid {
+            cells: "[.#@a-zA-Z]",
+            size: None,
+        },
+        20 => Shape::Grid {
+            cells: "[ .#A-Z]",
+            size: None,
+        },
+        22 => lines(r"deal into new stack|cut -?\d+|deal with increment \d+"),
+        24 => Shape::Grid {
+            cells: "[.#]",
+            size: Some((5, 5)),
+        },
+        _ => Shape::Any,
+    }
+}
+
+fn problem(line: Option<usize>, reason: &str) -> Problem {
+    Problem {
+        line,
+        reason: reason.to_owned(),
+    }
+}
+
+// Each line has to match the whole pattern
+fn check_lines(lines: &[&str], pattern: &str) -> Result<(), Problem> {
+    let regex = Regex::new(&format!("^(?:{})$", pattern)).expect("invalid shape pattern");
+    match lines.iter().position(|line| !regex.is_match(line)) {
+        Some(i) => Err(problem(Some(i + 1), &format!("doesn't match {}", pattern))),
+        None => Ok(()),
+    }
+}
+
+pub fn validate(year: u16, day: u8, contents: &str) -> Result<(), Problem> {
+    let trimmed = contents.trim_end_matches('\n');
+    if trimmed.trim().is_empty() {
+        return Err(problem(None, "empty"));
+    }
+    let start = trimmed.trim_start().to_lowercase();
+    if start.starts_with("<!doctype") || start.starts_with("<html") {
+        return Err(problem(None, "an HTML page rather than an input"));
+    }
+
+    let lines = trimmed.lines().collect::<Vec<_>>();
+    match shape(year, day) {
+        Shape::Intcode => {
+            if lines.len() != 1 {
+                return Err(problem(
+                    None,
+                    &format!("expected one line of Intcode, found {}", lines.len()),
+                ));
+            }
+            check_lines(&lines, r"-?\d+(,-?\d+)*")
+        }
+        Shape::Lines { pattern, count } => {
+            if let Some(count) = count.filter(|&count| count != lines.len()) {
+                return Err(problem(
+                    None,
+                    &format!("expected {} lines, found {}", count, lines.len()),
+                ));
+            }
+            check_lines(&lines, pattern)
+        }
+        Shape::Grid { cells, size } => {
+            check_lines(&lines, &format!("{}+", cells))?;
+            let width = lines[0].len();
+            if let Some(i) = lines.iter().position(|line| line.len() != width) {
+                return Err(problem(
+                    Some(i + 1),
+                    &format!("expected {} cells like the first row", width),
+                ));
+            }
+            match size {
+                Some(size) if size != (width, lines.len()) => Err(problem(
+                    None,
+                    &format!(
+                        "expected a {}x{} grid, found {}x{}",
+                        size.0,
+                        size.1,
+                        width,
+                        lines.len()
+                    ),
+                )),
+                _ => Ok(()),
+            }
+        }
+        Shape::Any => Ok(()),
+    }
+}
+
+// 64-bit FNV-1a, which is stable across Rust versions unlike the std hashers
+pub fn checksum(contents: &str) -> String {
+    let hash = contents.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {} {}", line, self.reason),
+            None => write!(f, "{}", self.reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_day_shapes() {
+        assert_eq!(validate(2019, 2, "1,9,10,3,2,3,11,0,99,30,40,50\n"), Ok(()));
+        assert_eq!(validate(2019, 10, ".#..#\n.....\n#####\n"), Ok(()));
+        assert_eq!(validate(2019, 3, "R8,U5,L5,D3\nU7,R6,D4,L4"), Ok(()));
+        assert_eq!(
+            validate(2019, 24, "....#\n#..#.\n#..##\n..#..\n#...."),
+            Ok(())
+        );
+
+        // Cut off part way through a number
+        let truncated = validate(2019, 5, "3,225,1,225,").unwrap_err();
+        assert_eq!(truncated.line, Some(1));
+        assert!(validate(2019, 10, ".#..#\n...\n").is_err());
+        assert!(validate(2019, 3, "R8,U5,L5,D3").is_err());
+        assert!(validate(2019, 24, "....#\n#..#.").is_err());
+        assert_eq!(
+            validate(2019, 12, "<x=-1, y=0, z=2>\n<x=2, y=-10>").map_err(|p| p.line),
+            Err(Some(2))
+        );
+    }
+
+    #[test]
+    fn rejects_html_and_empty_inputs() {
+        let page = "<!DOCTYPE html>\n<html><a href=\"/auth/login\">[Log In]</a></html>";
+        assert!(validate(2019, 1, page).is_err());
+        assert!(validate(2020, 1, page).is_err());
+        assert!(validate(2020, 1, "\n\n").is_err());
+        assert_eq!(validate(2020, 1, "anything"), Ok(()));
+    }
+
+    #[test]
+    fn checksums_are_stable() {
+        assert_eq!(checksum(""), "cbf29ce484222325");
+        assert_eq!(checksum("a"), "af63dc4c8601ec8c");
+        assert_ne!(checksum("1,2,3"), checksum("1,2,3,"));
+    }
+}
@@ -1,3 +1,6 @@
+pub mod ascii;
+pub mod symbolic;
+
 use std::fmt::Display;
 
 use crossbeam::channel::{unbounded, Receiver, Sender};
@@ -10,9 +13,10 @@ pub struct IntcodeComputer<T: Num> {
     relative_base: isize,
     incoming: Option<Receiver<T>>,
     outgoing: Option<Sender<(T, T)>>,
+    trace: bool,
 }
 
-#[derive(Primitive)]
+#[derive(Debug, Primitive)]
 pub enum IntcodeOpcode {
     Add = 1,
     Mult = 2,
@@ -26,7 +30,15 @@ pub enum IntcodeOpcode {
     Halt = 99,
 }
 
-#[derive(Primitive)]
+// Why `run_until_input` stopped
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Status {
+    Halted,
+    WantsInput,
+    Running,
+}
+
+#[derive(Primitive, Copy, Clone)]
 enum ParamMode {
     Position = 0,
     Immediate = 1,
@@ -48,6 +60,7 @@ where
             relative_base: 0,
             incoming: None,
             outgoing: None,
+            trace: false,
         }
     }
 
@@ -63,26 +76,66 @@ where
                 relative_base: 0,
                 incoming: Some(r_input),
                 outgoing: Some(s_output),
+                trace: false,
             },
             s_input,
             r_output,
         )
     }
 
+    // Prints each instruction to stderr as it's executed
+    pub fn set_trace(&mut self, trace: bool) {
+        self.trace = trace;
+    }
+
     pub fn run(&mut self) {
         while self.execute_instruction().unwrap() {}
     }
 
+    pub fn try_run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        while self.execute_instruction()? {}
+        Ok(())
+    }
+
+    // Runs until the program halts or wants input that hasn't been sent yet, or until it's run
+    // the given number of instructions without doing either, so a program stuck in a loop can't
+    // hang the caller
+    pub fn run_until_input(&mut self, limit: usize) -> Result<Status, Box<dyn std::error::Error>> {
+        for _ in 0..limit {
+            if self.wants_input() {
+                return Ok(Status::WantsInput);
+            }
+            if !self.execute_instruction()? {
+                return Ok(Status::Halted);
+            }
+        }
+        Ok(Status::Running)
+    }
+
+    fn wants_input(&self) -> bool {
+        self.mem
+            .get(self.instr_ptr)
+            .is_some_and(|&word| word % 100.into() == 3.into())
+            && self.incoming.as_ref().is_some_and(Receiver::is_empty)
+    }
+
     fn execute_instruction(&mut self) -> Result<bool, Box<dyn std::error::Error>> {
         let i = self.instr_ptr;
 
         // Get opcode and param indexes
-        let opcode = IntcodeOpcode::from_u8(cast(self.mem[i] % 100.into()).unwrap()).unwrap();
-        let modes = Self::get_modes(self.mem[i] / 100.into(), &opcode);
-        let indices: Vec<usize> = (i + 1..)
+        let word = *self.mem.get(i).ok_or("instruction pointer out of range")?;
+        let opcode = cast(word % 100.into())
+            .and_then(IntcodeOpcode::from_u8)
+            .ok_or("invalid opcode")?;
+        let modes = Self::get_modes(word / 100.into(), &opcode);
+        let indices = (i + 1..)
             .zip(modes.iter())
             .map(|(index, mode)| Self::fetch_param_index(self, index, mode))
-            .collect();
+            .collect::<Result<Vec<usize>, _>>()?;
+
+        if self.trace {
+            self.trace_instruction(&opcode, &indices);
+        }
 
         // Perform operation
         match opcode {
@@ -93,7 +146,7 @@ where
                 self.mem[indices[2]] = self.mem[indices[0]] * self.mem[indices[1]];
             }
             IntcodeOpcode::Input => {
-                self.mem[indices[0]] = self.receive_input();
+                self.mem[indices[0]] = self.receive_input()?;
             }
             IntcodeOpcode::Output => self.send_output(cast(i).unwrap(), self.mem[indices[0]]),
             IntcodeOpcode::JumpEq => {
@@ -125,16 +178,48 @@ where
         Ok(true)
     }
 
-    fn fetch_param_index(&self, index: usize, param_mode: &ParamMode) -> usize {
-        match param_mode {
-            ParamMode::Position => cast(self.mem[index]).unwrap(),
-            ParamMode::Immediate => index,
+    // Negative addresses and ones past the end of memory are errors rather than panics
+    fn fetch_param_index(
+        &self,
+        index: usize,
+        param_mode: &ParamMode,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let param = *self.mem.get(index).ok_or("parameter out of range")?;
+        let address = match param_mode {
+            ParamMode::Position => cast(param),
+            ParamMode::Immediate => Some(index),
             ParamMode::Relative => {
-                (self.relative_base + cast::<_, isize>(self.mem[index]).unwrap()) as usize
+                cast::<_, isize>(param).and_then(|offset| cast(self.relative_base + offset))
             }
+        };
+        match address {
+            Some(address) if address < self.mem.len() => Ok(address),
+            _ => Err(format!("address out of range at {}", self.instr_ptr).into()),
         }
     }
 
+    fn trace_instruction(&self, opcode: &IntcodeOpcode, indices: &[usize]) {
+        let i = self.instr_ptr;
+        let raw = self.mem[i..=i + opcode.num_of_params()]
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let params = indices
+            .iter()
+            .map(|index| format!("[{}]={}", index, self.mem[*index]))
+            .collect::<Vec<_>>()
+            .join(" ");
+        eprintln!(
+            "{:>6} rb={:<6} {:<8} {:<24} {}",
+            i,
+            self.relative_base,
+            format!("{:?}", opcode),
+            raw,
+            params
+        );
+    }
+
     fn get_modes(ms: T, opcode: &IntcodeOpcode) -> Vec<ParamMode> {
         if opcode.num_of_params() == 0 {
             return Vec::new();
@@ -155,9 +240,9 @@ where
         }
     }
 
-    fn receive_input(&self) -> T {
+    fn receive_input(&self) -> Result<T, Box<dyn std::error::Error>> {
         match &self.incoming {
-            Some(receiver) => receiver.recv().unwrap(),
+            Some(receiver) => Ok(receiver.recv()?),
             _ => panic!("Can't receive input - handlers haven't been configured"),
         }
     }
@@ -173,3 +258,222 @@ impl IntcodeOpcode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Converts program literals into the word size under test
+    fn words<T: NumCast>(xs: &[i64]) -> Vec<T> {
+        xs.iter().map(|x| cast(*x).unwrap()).collect()
+    }
+
+    // Runs a program to completion and returns its memory, cut to the program length
+    fn run_to_halt<T>(program: &[i64]) -> Vec<T>
+    where
+        T: Num + Copy + Clone + PartialOrd + NumCast + Display + From<bool>,
+        u8: Into<T>,
+    {
+        let mut computer = IntcodeComputer::new(words(program));
+        computer.run();
+        computer.mem.truncate(program.len());
+        computer.mem
+    }
+
+    // Runs a program with the given inputs queued up and returns every value it outputs
+    fn run_with_input<T>(program: &[i64], inputs: &[i64]) -> Vec<T>
+    where
+        T: Num + Copy + Clone + PartialOrd + NumCast + Display + From<bool>,
+        u8: Into<T>,
+    {
+        let (mut computer, s, r) = IntcodeComputer::with_io(words(program));
+        for input in words::<T>(inputs) {
+            s.send(input).unwrap();
+        }
+        computer.run();
+        drop(computer);
+        r.iter().map(|(_, val)| val).collect()
+    }
+
+    fn assert_halts_with<T>(program: &[i64], expected: &[i64])
+    where
+        T: Num + Copy + Clone + PartialOrd + NumCast + Display + From<bool> + std::fmt::Debug,
+        u8: Into<T>,
+    {
+        assert_eq!(run_to_halt::<T>(program), words::<T>(expected));
+    }
+
+    fn assert_outputs<T>(program: &[i64], inputs: &[i64], expected: &[i64])
+    where
+        T: Num + Copy + Clone + PartialOrd + NumCast + Display + From<bool> + std::fmt::Debug,
+        u8: Into<T>,
+    {
+        assert_eq!(run_with_input::<T>(program, inputs), words::<T>(expected));
+    }
+
+    // Runs each check against both of the word sizes used by the solutions
+    macro_rules! both_word_sizes {
+        ($check: ident ($($args: expr),*)) => {
+            $check::<i32>($($args),*);
+            $check::<i64>($($args),*);
+        };
+    }
+
+    #[test]
+    fn day_02_examples() {
+        both_word_sizes!(assert_halts_with(
+            &[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50],
+            &[3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50]
+        ));
+        both_word_sizes!(assert_halts_with(&[1, 0, 0, 0, 99], &[2, 0, 0, 0, 99]));
+        both_word_sizes!(assert_halts_with(&[2, 3, 0, 3, 99], &[2, 3, 0, 6, 99]));
+        both_word_sizes!(assert_halts_with(
+            &[2, 4, 4, 5, 99, 0],
+            &[2, 4, 4, 5, 99, 9801]
+        ));
+        both_word_sizes!(assert_halts_with(
+            &[1, 1, 1, 4, 99, 5, 6, 0, 99],
+            &[30, 1, 1, 4, 2, 5, 6, 0, 99]
+        ));
+    }
+
+    #[test]
+    fn day_05_io_and_immediate_mode() {
+        both_word_sizes!(assert_outputs(&[3, 0, 4, 0, 99], &[42], &[42]));
+        both_word_sizes!(assert_outputs(&[3, 0, 4, 0, 99], &[-7], &[-7]));
+        both_word_sizes!(assert_halts_with(
+            &[1002, 4, 3, 4, 33],
+            &[1002, 4, 3, 4, 99]
+        ));
+        both_word_sizes!(assert_halts_with(
+            &[1101, 100, -1, 4, 0],
+            &[1101, 100, -1, 4, 99]
+        ));
+    }
+
+    #[test]
+    fn day_05_comparisons() {
+        const EQUALS_POSITION: &[i64] = &[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+        const LESS_THAN_POSITION: &[i64] = &[3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8];
+        const EQUALS_IMMEDIATE: &[i64] = &[3, 3, 1108, -1, 8, 3, 4, 3, 99];
+        const LESS_THAN_IMMEDIATE: &[i64] = &[3, 3, 1107, -1, 8, 3, 4, 3, 99];
+
+        for (input, equal, less) in [(7, 0, 1), (8, 1, 0), (9, 0, 0), (-8, 0, 1)] {
+            both_word_sizes!(assert_outputs(EQUALS_POSITION, &[input], &[equal]));
+            both_word_sizes!(assert_outputs(EQUALS_IMMEDIATE, &[input], &[equal]));
+            both_word_sizes!(assert_outputs(LESS_THAN_POSITION, &[input], &[less]));
+            both_word_sizes!(assert_outputs(LESS_THAN_IMMEDIATE, &[input], &[less]));
+        }
+    }
+
+    #[test]
+    fn day_05_jumps() {
+        const JUMP_POSITION: &[i64] = &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9];
+        const JUMP_IMMEDIATE: &[i64] = &[3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+
+        for (input, expected) in [(0, 0), (1, 1), (-3, 1), (100, 1)] {
+            both_word_sizes!(assert_outputs(JUMP_POSITION, &[input], &[expected]));
+            both_word_sizes!(assert_outputs(JUMP_IMMEDIATE, &[input], &[expected]));
+        }
+    }
+
+    #[test]
+    fn day_05_larger_example() {
+        const PROGRAM: &[i64] = &[
+            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+            20, 1105, 1, 46, 98, 99,
+        ];
+
+        for (input, expected) in [(-5, 999), (7, 999), (8, 1000), (9, 1001), (5000, 1001)] {
+            both_word_sizes!(assert_outputs(PROGRAM, &[input], &[expected]));
+        }
+    }
+
+    #[test]
+    fn stops_for_input() {
+        // Echoes inputs until given 0
+        let program = [3, 9, 4, 9, 1005, 9, 0, 99, 0, 0];
+        let (mut computer, s, r) = IntcodeComputer::with_io(program.to_vec());
+        assert_eq!(computer.run_until_input(100).unwrap(), Status::WantsInput);
+        s.send(5).unwrap();
+        assert_eq!(computer.run_until_input(100).unwrap(), Status::WantsInput);
+        assert_eq!(r.try_recv().unwrap().1, 5);
+        s.send(0).unwrap();
+        assert_eq!(computer.run_until_input(100).unwrap(), Status::Halted);
+
+        // An endless loop runs out of instructions
+        let mut computer = IntcodeComputer::new(vec![1105, 1, 0]);
+        assert_eq!(computer.run_until_input(100).unwrap(), Status::Running);
+    }
+
+    #[test]
+    fn reports_bad_addresses() {
+        // Negative position, negative relative address, and past the end of memory
+        for program in [vec![4, -1, 99], vec![204, -1, 99], vec![1105, 1, 70000]] {
+            let (mut computer, _s, _r) = IntcodeComputer::with_io(program);
+            assert!(computer.try_run().is_err());
+        }
+    }
+
+    #[test]
+    fn day_09_quine() {
+        const QUINE: &[i64] = &[
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        both_word_sizes!(assert_outputs(QUINE, &[], QUINE));
+    }
+
+    #[test]
+    fn day_09_large_numbers() {
+        // These only fit in 64-bit words
+        let output = run_with_input::<i64>(&[1102, 34915192, 34915192, 7, 4, 7, 99, 0], &[]);
+        assert_eq!(output, vec![1_219_070_632_396_864]);
+        assert_eq!(output[0].to_string().len(), 16);
+
+        assert_outputs::<i64>(&[104, 1125899906842624, 99], &[], &[1125899906842624]);
+    }
+
+    #[test]
+    fn relative_mode_writes() {
+        // Add and input both write through the relative base
+        both_word_sizes!(assert_outputs(
+            &[109, 10, 21101, 3, 4, 0, 204, 0, 99],
+            &[],
+            &[7]
+        ));
+        both_word_sizes!(assert_outputs(
+            &[109, 20, 203, -5, 204, -5, 99],
+            &[55],
+            &[55]
+        ));
+        // Comparisons write their result through the relative base too
+        both_word_sizes!(assert_outputs(
+            &[109, 30, 21108, 4, 4, 1, 21107, 5, 4, 2, 204, 1, 204, 2, 99],
+            &[],
+            &[1, 0]
+        ));
+    }
+
+    #[test]
+    fn negative_relative_base() {
+        // Relative base adjusted below zero, reaching back into the program
+        both_word_sizes!(assert_outputs(
+            &[109, -1, 204, 1, 204, 7, 99],
+            &[],
+            &[109, 99]
+        ));
+        // Negative offsets from a positive base
+        both_word_sizes!(assert_outputs(&[109, 10, 204, -6, 99], &[], &[99]));
+        // Base adjustments accumulate, including back past zero
+        both_word_sizes!(assert_outputs(&[109, 5, 109, -7, 204, 2, 99], &[], &[109]));
+    }
+
+    #[test]
+    fn outputs_carry_instruction_address() {
+        let (mut computer, _, r) = IntcodeComputer::with_io(vec![104, 1, 104, 2, 99]);
+        computer.run();
+        drop(computer);
+        assert_eq!(r.iter().collect::<Vec<(i32, i32)>>(), vec![(0, 1), (2, 2)]);
+    }
+}
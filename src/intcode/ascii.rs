@@ -0,0 +1,131 @@
+use std::{convert::TryFrom, error::Error};
+
+use crossbeam::channel::{Receiver, RecvError, Sender};
+
+use super::{IntcodeComputer, Status};
+
+// Running programs that talk in lines of ASCII text, like the day 17 vacuum robot and the day 21
+// springdroid. Anything a program outputs outside the ASCII range is an answer rather than text.
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Transcript {
+    pub text: String,
+    pub values: Vec<i64>,
+}
+
+pub fn encode(text: &str) -> impl Iterator<Item = i64> + '_ {
+    text.bytes().map(i64::from)
+}
+
+// Splits output into text and the values that aren't ASCII
+pub fn decode(output: impl IntoIterator<Item = i64>) -> Transcript {
+    let mut transcript = Transcript::default();
+    for value in output {
+        match u8::try_from(value) {
+            Ok(c) if c.is_ascii() => transcript.text.push(char::from(c)),
+            _ => transcript.values.push(value),
+        }
+    }
+    transcript
+}
+
+// Runs the program until it halts, with all of its input given up front. Asking for more input
+// than that is an error, since nothing else is coming, as is anything else the computer fails on.
+pub fn run(mem: &[i64], input: &str) -> Result<Transcript, Box<dyn Error>> {
+    let (mut computer, sender, receiver) = IntcodeComputer::with_io(mem.to_vec());
+    for value in encode(input) {
+        sender.send(value)?;
+    }
+    drop(sender);
+
+    let result = computer.try_run();
+    drop(computer);
+    let transcript = decode(receiver.iter().map(|(_, value)| value));
+    match result {
+        Ok(()) => Ok(transcript),
+        // Input only fails once everything sent has been read and the sender is gone
+        Err(e) if e.is::<RecvError>() => {
+            Err(format!("Program wanted more input after:\n{}", transcript.text).into())
+        }
+        Err(e) => Err(format!("Intcode error: {}", e).into()),
+    }
+}
+
+// A program played a line at a time, like the day 25 text adventure. Each line runs the program
+// until it next asks for input, halts, or runs `limit` instructions without doing either.
+pub struct Session {
+    computer: IntcodeComputer<i64>,
+    sender: Sender<i64>,
+    receiver: Receiver<(i64, i64)>,
+    limit: usize,
+}
+
+impl Session {
+    pub fn new(mem: &[i64], limit: usize) -> Self {
+        let (computer, sender, receiver) = IntcodeComputer::with_io(mem.to_vec());
+        Session {
+            computer,
+            sender,
+            receiver,
+            limit,
+        }
+    }
+
+    // Sends the input, which should end with a newline unless it's empty, and returns what the
+    // program says back
+    pub fn send(&mut self, input: &str) -> Result<(Transcript, Status), Box<dyn Error>> {
+        for value in encode(input) {
+            self.sender.send(value)?;
+        }
+        let status = self.computer.run_until_input(self.limit)?;
+        Ok((
+            decode(self.receiver.try_iter().map(|(_, value)| value)),
+            status,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn echoes_text_and_values() {
+        // Echoes three characters, then outputs 1000
+        let program = [3, 20, 4, 20, 3, 20, 4, 20, 3, 20, 4, 20, 104, 1000, 99];
+        let transcript = run(&program, "hi\n").unwrap();
+        assert_eq!(transcript.text, "hi\n");
+        assert_eq!(transcript.values, vec![1000]);
+
+        assert!(run(&program, "h")
+            .unwrap_err()
+            .to_string()
+            .starts_with("Program wanted more input after:\nh"));
+    }
+
+    #[test]
+    fn reports_intcode_errors() {
+        // Outputs a character, then hits an invalid opcode
+        let error = run(&[104, 65, 42], "").unwrap_err().to_string();
+        assert_eq!(error, "Intcode error: invalid opcode");
+    }
+
+    #[test]
+    fn plays_a_line_at_a_time() {
+        // Prints > and echoes a character until given a newline
+        let program = [104, 62, 3, 20, 4, 20, 1008, 20, 10, 21, 1006, 21, 0, 99];
+        let mut session = Session::new(&program, 100);
+        assert_eq!(
+            session.send("").unwrap(),
+            (decode(encode(">")), Status::WantsInput)
+        );
+        assert_eq!(
+            session.send("a").unwrap(),
+            (decode(encode("a>")), Status::WantsInput)
+        );
+        assert_eq!(
+            session.send("\n").unwrap(),
+            (decode(encode("\n")), Status::Halted)
+        );
+    }
+}
@@ -0,0 +1,733 @@
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt::{self, Display},
+    ops::RangeInclusive,
+};
+
+use itertools::Itertools;
+use num_traits::{cast::cast, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Num, NumCast, Zero};
+
+use super::{IntcodeComputer, IntcodeOpcode, ParamMode, Status};
+
+// Executes Intcode with some memory cells or inputs left as unknowns, building up expressions
+// for every value that depends on them. When the value we care about ends up linear in the
+// unknowns it can be solved for directly, otherwise we fall back to running every combination.
+// Arithmetic on constants is checked, and anything that would overflow is left unsolved, so it
+// goes to the search too.
+
+// Steps a run can take, symbolic or concrete, before it's taken to be stuck in a loop
+const MAX_STEPS: usize = 1_000_000;
+
+#[derive(Debug, Clone)]
+pub enum Expr<T> {
+    Const(T),
+    Unknown(usize),
+    // A read through an address that depends on an unknown
+    Load(Box<Expr<T>>),
+    Add(Box<Expr<T>>, Box<Expr<T>>),
+    Mult(Box<Expr<T>>, Box<Expr<T>>),
+    LessThan(Box<Expr<T>>, Box<Expr<T>>),
+    Equals(Box<Expr<T>>, Box<Expr<T>>),
+}
+
+// constant + sum(coeffs[i] * unknown_i)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Linear<T> {
+    pub constant: T,
+    pub coeffs: Vec<T>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Source {
+    Cell(usize),
+    Input(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct Unknown<T> {
+    pub source: Source,
+    pub range: RangeInclusive<T>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Target {
+    Cell(usize),
+    Output(usize),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Method {
+    Linear,
+    Search,
+}
+
+#[derive(Debug, Clone)]
+pub struct Solution<T> {
+    // Values for each unknown, in the order they were declared
+    pub values: Vec<T>,
+    pub method: Method,
+    // Number of concrete Intcode runs needed to find (and check) the solution
+    pub runs: usize,
+}
+
+#[derive(Debug)]
+pub enum SymbolicError {
+    InvalidOpcode(usize),
+    SymbolicInstruction(usize),
+    SymbolicAddress(usize),
+    SymbolicBranch(usize),
+    // Negative, or past the end of memory
+    AddressOutOfRange(usize),
+    MissingInput(usize),
+    MissingTarget(Target),
+    StepLimit,
+    NoSolution,
+}
+
+#[derive(Debug, Clone)]
+enum InputSlot<T> {
+    Known(T),
+    Unknown(usize),
+}
+
+#[derive(Debug)]
+pub struct SymbolicComputer<T> {
+    program: Vec<T>,
+    mem: Vec<Expr<T>>,
+    instr_ptr: usize,
+    relative_base: isize,
+    unknowns: Vec<Unknown<T>>,
+    input_plan: Vec<InputSlot<T>>,
+    inputs: VecDeque<Expr<T>>,
+    outputs: Vec<Expr<T>>,
+}
+
+impl<T> Expr<T>
+where
+    T: Num + Copy + Clone + PartialOrd + NumCast + Display + From<bool>,
+    T: CheckedAdd + CheckedSub + CheckedMul + CheckedDiv,
+    u8: Into<T>,
+{
+    pub fn as_const(&self) -> Option<T> {
+        match self {
+            Expr::Const(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    fn add(a: Self, b: Self) -> Self {
+        match (a.as_const(), b.as_const()) {
+            (Some(x), Some(y)) if x.checked_add(&y).is_some() => Expr::Const(x + y),
+            (Some(x), _) if x.is_zero() => b,
+            (_, Some(y)) if y.is_zero() => a,
+            _ => Expr::Add(Box::new(a), Box::new(b)),
+        }
+    }
+
+    fn mult(a: Self, b: Self) -> Self {
+        match (a.as_const(), b.as_const()) {
+            (Some(x), Some(y)) if x.checked_mul(&y).is_some() => Expr::Const(x * y),
+            (Some(x), _) | (_, Some(x)) if x.is_zero() => Expr::Const(T::zero()),
+            (Some(x), _) if x.is_one() => b,
+            (_, Some(y)) if y.is_one() => a,
+            _ => Expr::Mult(Box::new(a), Box::new(b)),
+        }
+    }
+
+    fn less_than(a: Self, b: Self) -> Self {
+        match (a.as_const(), b.as_const()) {
+            (Some(x), Some(y)) => Expr::Const((x < y).into()),
+            _ => Expr::LessThan(Box::new(a), Box::new(b)),
+        }
+    }
+
+    fn equals(a: Self, b: Self) -> Self {
+        match (a.as_const(), b.as_const()) {
+            (Some(x), Some(y)) => Expr::Const((x == y).into()),
+            _ => Expr::Equals(Box::new(a), Box::new(b)),
+        }
+    }
+
+    // Flattens the expression into a linear form, if it is one whose constant and coefficients
+    // fit in T
+    pub fn linear(&self, num_unknowns: usize) -> Option<Linear<T>> {
+        match self {
+            Expr::Const(x) => Some(Linear {
+                constant: *x,
+                coeffs: vec![T::zero(); num_unknowns],
+            }),
+            Expr::Unknown(i) => {
+                let mut coeffs = vec![T::zero(); num_unknowns];
+                *coeffs.get_mut(*i)? = T::one();
+                Some(Linear {
+                    constant: T::zero(),
+                    coeffs,
+                })
+            }
+            Expr::Add(a, b) => {
+                let (a, b) = (a.linear(num_unknowns)?, b.linear(num_unknowns)?);
+                Some(Linear {
+                    constant: a.constant.checked_add(&b.constant)?,
+                    coeffs: a
+                        .coeffs
+                        .iter()
+                        .zip(&b.coeffs)
+                        .map(|(x, y)| x.checked_add(y))
+                        .collect::<Option<_>>()?,
+                })
+            }
+            Expr::Mult(a, b) => {
+                let (a, b) = (a.linear(num_unknowns)?, b.linear(num_unknowns)?);
+                let (scale, l) = if a.coeffs.iter().all(Zero::is_zero) {
+                    (a.constant, b)
+                } else if b.coeffs.iter().all(Zero::is_zero) {
+                    (b.constant, a)
+                } else {
+                    return None;
+                };
+                Some(Linear {
+                    constant: l.constant.checked_mul(&scale)?,
+                    coeffs: l
+                        .coeffs
+                        .iter()
+                        .map(|c| c.checked_mul(&scale))
+                        .collect::<Option<_>>()?,
+                })
+            }
+            Expr::Load(_) | Expr::LessThan(_, _) | Expr::Equals(_, _) => None,
+        }
+    }
+}
+
+impl<T: Display> Display for Expr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Const(x) => write!(f, "{}", x),
+            Expr::Unknown(i) => write!(f, "x{}", i),
+            Expr::Load(addr) => write!(f, "mem[{}]", addr),
+            Expr::Add(a, b) => write!(f, "({} + {})", a, b),
+            Expr::Mult(a, b) => write!(f, "({} * {})", a, b),
+            Expr::LessThan(a, b) => write!(f, "({} < {})", a, b),
+            Expr::Equals(a, b) => write!(f, "({} == {})", a, b),
+        }
+    }
+}
+
+impl<T> SymbolicComputer<T>
+where
+    T: Num + Copy + Clone + PartialOrd + NumCast + Display + From<bool>,
+    T: CheckedAdd + CheckedSub + CheckedMul + CheckedDiv,
+    u8: Into<T>,
+{
+    pub fn new(program: Vec<T>) -> Self {
+        SymbolicComputer {
+            program,
+            mem: Vec::new(),
+            instr_ptr: 0,
+            relative_base: 0,
+            unknowns: Vec::new(),
+            input_plan: Vec::new(),
+            inputs: VecDeque::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    // Marks a memory cell as unknown, returning its index in the solution
+    pub fn unknown_cell(&mut self, address: usize, range: RangeInclusive<T>) -> usize {
+        self.unknowns.push(Unknown {
+            source: Source::Cell(address),
+            range,
+        });
+        self.unknowns.len() - 1
+    }
+
+    // Queues an unknown input, returning its index in the solution
+    pub fn unknown_input(&mut self, range: RangeInclusive<T>) -> usize {
+        let id = self.unknowns.len();
+        self.unknowns.push(Unknown {
+            source: Source::Input(self.input_plan.len()),
+            range,
+        });
+        self.input_plan.push(InputSlot::Unknown(id));
+        id
+    }
+
+    pub fn input(&mut self, value: T) {
+        self.input_plan.push(InputSlot::Known(value));
+    }
+
+    pub fn unknowns(&self) -> &[Unknown<T>] {
+        &self.unknowns
+    }
+
+    pub fn outputs(&self) -> &[Expr<T>] {
+        &self.outputs
+    }
+
+    pub fn cell(&self, address: usize) -> Result<&Expr<T>, SymbolicError> {
+        self.mem
+            .get(address)
+            .ok_or(SymbolicError::AddressOutOfRange(address))
+    }
+
+    pub fn target(&self, target: Target) -> Result<&Expr<T>, SymbolicError> {
+        match target {
+            Target::Cell(address) => self.cell(address),
+            Target::Output(n) => self
+                .outputs
+                .get(n)
+                .ok_or(SymbolicError::MissingTarget(target)),
+        }
+    }
+
+    // Runs the program symbolically until it halts
+    pub fn run(&mut self) -> Result<(), SymbolicError> {
+        self.mem = self.program.iter().map(|x| Expr::Const(*x)).collect();
+        self.mem
+            .resize(IntcodeComputer::<T>::MEM_SIZE, Expr::Const(T::zero()));
+        for (i, unknown) in self.unknowns.iter().enumerate() {
+            if let Source::Cell(address) = unknown.source {
+                *self
+                    .mem
+                    .get_mut(address)
+                    .ok_or(SymbolicError::AddressOutOfRange(address))? = Expr::Unknown(i);
+            }
+        }
+
+        self.inputs = self
+            .input_plan
+            .iter()
+            .map(|slot| match slot {
+                InputSlot::Known(x) => Expr::Const(*x),
+                InputSlot::Unknown(i) => Expr::Unknown(*i),
+            })
+            .collect();
+        self.outputs.clear();
+        self.instr_ptr = 0;
+        self.relative_base = 0;
+
+        for _ in 0..MAX_STEPS {
+            if !self.execute_instruction()? {
+                return Ok(());
+            }
+        }
+        Err(SymbolicError::StepLimit)
+    }
+
+    // Finds values for the unknowns that make the target equal to `value`
+    pub fn solve(&mut self, target: Target, value: T) -> Result<Solution<T>, SymbolicError> {
+        let linear = match self.run() {
+            Ok(()) => self
+                .target(target)?
+                .linear(self.unknowns.len())
+                .and_then(|l| self.solve_linear(&l, value)),
+            Err(SymbolicError::MissingInput(i)) => return Err(SymbolicError::MissingInput(i)),
+            Err(_) => None,
+        };
+
+        if let Some(values) = linear {
+            if self.check(&values, target) == Some(value) {
+                return Ok(Solution {
+                    values,
+                    method: Method::Linear,
+                    runs: 1,
+                });
+            }
+        }
+
+        self.search(target, value)
+    }
+
+    fn solve_linear(&self, linear: &Linear<T>, value: T) -> Option<Vec<T>> {
+        let ranges = self.unknowns.iter().map(|u| u.range.clone()).collect_vec();
+        let rhs = value.checked_sub(&linear.constant)?;
+
+        // Solve for the last unknown that matters, enumerating the rest
+        let pivot = match linear.coeffs.iter().rposition(|c| !c.is_zero()) {
+            Some(p) => p,
+            None if rhs.is_zero() => return Some(ranges.iter().map(|r| *r.start()).collect()),
+            None => return None,
+        };
+
+        let free = (0..ranges.len())
+            .map(|i| {
+                if i == pivot {
+                    vec![T::zero()]
+                } else if linear.coeffs[i].is_zero() {
+                    vec![*ranges[i].start()]
+                } else {
+                    values_in(&ranges[i])
+                }
+            })
+            .multi_cartesian_product();
+
+        for mut values in free {
+            let remainder = values
+                .iter()
+                .zip(&linear.coeffs)
+                .try_fold(rhs, |acc, (x, c)| acc.checked_sub(&x.checked_mul(c)?));
+            let coeff = linear.coeffs[pivot];
+
+            // Values that overflow can't be the answer, since the program couldn't produce them
+            let x = match remainder.and_then(|r| Some((r, r.checked_div(&coeff)?))) {
+                Some((r, x)) if x * coeff == r => x,
+                _ => continue,
+            };
+            if ranges[pivot].contains(&x) {
+                values[pivot] = x;
+                return Some(values);
+            }
+        }
+
+        None
+    }
+
+    fn search(&self, target: Target, value: T) -> Result<Solution<T>, SymbolicError> {
+        for (i, values) in self
+            .unknowns
+            .iter()
+            .map(|u| values_in(&u.range))
+            .multi_cartesian_product()
+            .enumerate()
+        {
+            if self.check(&values, target) == Some(value) {
+                return Ok(Solution {
+                    values,
+                    method: Method::Search,
+                    runs: i + 1,
+                });
+            }
+        }
+        Err(SymbolicError::NoSolution)
+    }
+
+    // Runs the program concretely with the given values for the unknowns. Values that make it
+    // fail, run out of input or loop for too long don't reach anything.
+    fn check(&self, values: &[T], target: Target) -> Option<T> {
+        let mut mem = self.program.clone();
+        mem.resize(IntcodeComputer::<T>::MEM_SIZE.max(mem.len()), T::zero());
+        for (unknown, value) in self.unknowns.iter().zip(values) {
+            if let Source::Cell(address) = unknown.source {
+                *mem.get_mut(address)? = *value;
+            }
+        }
+
+        let (mut computer, s, r) = IntcodeComputer::with_io(mem);
+        for slot in &self.input_plan {
+            let input = match slot {
+                InputSlot::Known(x) => *x,
+                InputSlot::Unknown(i) => values[*i],
+            };
+            s.send(input).ok()?;
+        }
+        drop(s);
+
+        if computer.run_until_input(MAX_STEPS).ok()? != Status::Halted {
+            return None;
+        }
+        match target {
+            Target::Cell(address) => computer.mem.get(address).copied(),
+            Target::Output(n) => r.try_iter().nth(n).map(|(_, val)| val),
+        }
+    }
+
+    fn execute_instruction(&mut self) -> Result<bool, SymbolicError> {
+        let i = self.instr_ptr;
+
+        let word = self
+            .mem
+            .get(i)
+            .ok_or(SymbolicError::AddressOutOfRange(i))?
+            .as_const()
+            .ok_or(SymbolicError::SymbolicInstruction(i))?;
+        let opcode = cast(word % 100.into())
+            .and_then(num_traits::FromPrimitive::from_u8)
+            .ok_or(SymbolicError::InvalidOpcode(i))?;
+        let modes = IntcodeComputer::<T>::get_modes(word / 100.into(), &opcode);
+        let params = (i + 1..).zip(modes).collect_vec();
+
+        match opcode {
+            IntcodeOpcode::Add => {
+                let val = Expr::add(self.read(params[0])?, self.read(params[1])?);
+                self.write(params[2], val)?;
+            }
+            IntcodeOpcode::Mult => {
+                let val = Expr::mult(self.read(params[0])?, self.read(params[1])?);
+                self.write(params[2], val)?;
+            }
+            IntcodeOpcode::Input => {
+                let val = self
+                    .inputs
+                    .pop_front()
+                    .ok_or(SymbolicError::MissingInput(i))?;
+                self.write(params[0], val)?;
+            }
+            IntcodeOpcode::Output => {
+                let val = self.read(params[0])?;
+                self.outputs.push(val);
+            }
+            IntcodeOpcode::JumpEq | IntcodeOpcode::JumpNeq => {
+                let condition = self
+                    .read(params[0])?
+                    .as_const()
+                    .ok_or(SymbolicError::SymbolicBranch(i))?;
+                let jump_if_zero = matches!(opcode, IntcodeOpcode::JumpNeq);
+                if condition.is_zero() == jump_if_zero {
+                    self.instr_ptr = self
+                        .read(params[1])?
+                        .as_const()
+                        .and_then(cast)
+                        .ok_or(SymbolicError::SymbolicAddress(i))?;
+                    return Ok(true);
+                }
+            }
+            IntcodeOpcode::LessThan => {
+                let val = Expr::less_than(self.read(params[0])?, self.read(params[1])?);
+                self.write(params[2], val)?;
+            }
+            IntcodeOpcode::Equals => {
+                let val = Expr::equals(self.read(params[0])?, self.read(params[1])?);
+                self.write(params[2], val)?;
+            }
+            IntcodeOpcode::RelBase => {
+                self.relative_base += self
+                    .read(params[0])?
+                    .as_const()
+                    .and_then(cast::<T, isize>)
+                    .ok_or(SymbolicError::SymbolicAddress(i))?;
+            }
+            IntcodeOpcode::Halt => return Ok(false),
+        };
+
+        self.instr_ptr += 1 + opcode.num_of_params();
+        Ok(true)
+    }
+
+    // Resolves the address a parameter refers to, or None if it depends on an unknown
+    fn address(&self, (index, mode): (usize, ParamMode)) -> Result<Option<usize>, SymbolicError> {
+        let out_of_range = SymbolicError::AddressOutOfRange(self.instr_ptr);
+        let param = match self.mem.get(index) {
+            Some(param) => param,
+            None => return Err(out_of_range),
+        };
+        let address = match (mode, param.as_const()) {
+            (ParamMode::Immediate, _) => Some(index),
+            (_, None) => return Ok(None),
+            (ParamMode::Position, Some(address)) => cast(address),
+            (ParamMode::Relative, Some(offset)) => {
+                cast::<T, isize>(offset).and_then(|offset| cast(self.relative_base + offset))
+            }
+        };
+        match address {
+            Some(address) if address < self.mem.len() => Ok(Some(address)),
+            _ => Err(out_of_range),
+        }
+    }
+
+    fn read(&self, param: (usize, ParamMode)) -> Result<Expr<T>, SymbolicError> {
+        let (index, mode) = param;
+        if let Some(address) = self.address(param)? {
+            return Ok(self.mem[address].clone());
+        }
+
+        // The address depends on an unknown, so the value read is opaque
+        let address = match mode {
+            ParamMode::Relative => Expr::add(
+                Expr::Const(cast(self.relative_base).unwrap()),
+                self.mem[index].clone(),
+            ),
+            _ => self.mem[index].clone(),
+        };
+        Ok(Expr::Load(Box::new(address)))
+    }
+
+    fn write(&mut self, param: (usize, ParamMode), val: Expr<T>) -> Result<(), SymbolicError> {
+        let address = self
+            .address(param)?
+            .ok_or(SymbolicError::SymbolicAddress(self.instr_ptr))?;
+        self.mem[address] = val;
+        Ok(())
+    }
+}
+
+// Steps up to the end without going past it, so a range ending at T's maximum can't overflow
+fn values_in<T>(range: &RangeInclusive<T>) -> Vec<T>
+where
+    T: Num + Copy + PartialOrd,
+{
+    let mut values = Vec::new();
+    let mut x = *range.start();
+    while x <= *range.end() {
+        values.push(x);
+        if x == *range.end() {
+            break;
+        }
+        x = x + T::one();
+    }
+    values
+}
+
+impl Display for SymbolicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidOpcode(i) => write!(f, "invalid opcode at {}", i),
+            Self::SymbolicInstruction(i) => write!(f, "instruction at {} depends on an unknown", i),
+            Self::SymbolicAddress(i) => write!(f, "address used at {} depends on an unknown", i),
+            Self::SymbolicBranch(i) => write!(f, "jump at {} depends on an unknown", i),
+            Self::AddressOutOfRange(i) => write!(f, "address used at {} is out of range", i),
+            Self::MissingInput(i) => write!(f, "ran out of inputs at {}", i),
+            Self::MissingTarget(t) => write!(f, "target {:?} was never produced", t),
+            Self::StepLimit => write!(f, "gave up after {} steps", MAX_STEPS),
+            Self::NoSolution => write!(f, "no values for the unknowns reach the target"),
+        }
+    }
+}
+
+impl Error for SymbolicError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Like day 2: mem[0] = 100 * noun + verb, with a dead read through noun and verb first
+    const NOUN_VERB: &[i32] = &[1, 0, 0, 3, 2, 1, 15, 3, 1, 3, 2, 0, 99, 0, 0, 100];
+
+    #[test]
+    fn solves_linear_cells() {
+        let mut computer = SymbolicComputer::new(NOUN_VERB.to_vec());
+        let noun = computer.unknown_cell(1, 0..=99);
+        let verb = computer.unknown_cell(2, 0..=99);
+
+        let solution = computer.solve(Target::Cell(0), 1234).unwrap();
+        assert_eq!(solution.method, Method::Linear);
+        assert_eq!(solution.runs, 1);
+        assert_eq!((solution.values[noun], solution.values[verb]), (12, 34));
+
+        let linear = computer.cell(0).unwrap().linear(2).unwrap();
+        assert_eq!(linear.constant, 0);
+        assert_eq!(linear.coeffs, vec![100, 1]);
+    }
+
+    #[test]
+    fn solves_linear_inputs() {
+        // Outputs 5 * a + b
+        let program = vec![3, 20, 3, 21, 1002, 20, 5, 22, 1, 22, 21, 23, 4, 23, 99];
+        let mut computer = SymbolicComputer::new(program);
+        let a = computer.unknown_input(0..=9);
+        computer.input(3);
+
+        let solution = computer.solve(Target::Output(0), 38).unwrap();
+        assert_eq!(solution.method, Method::Linear);
+        assert_eq!(solution.values[a], 7);
+    }
+
+    #[test]
+    fn searches_through_comparisons() {
+        let mut computer = SymbolicComputer::new(vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 6]);
+        computer.unknown_input(0..=9);
+
+        let solution = computer.solve(Target::Output(0), 1).unwrap();
+        assert_eq!(solution.method, Method::Search);
+        assert_eq!(solution.values, vec![6]);
+        assert_eq!(solution.runs, 7);
+    }
+
+    #[test]
+    fn searches_through_branches() {
+        let program = vec![3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+        let mut computer = SymbolicComputer::new(program);
+        computer.unknown_input(-3..=3);
+
+        assert!(matches!(
+            computer.run(),
+            Err(SymbolicError::SymbolicBranch(2))
+        ));
+        let solution = computer.solve(Target::Output(0), 0).unwrap();
+        assert_eq!(solution.method, Method::Search);
+        assert_eq!(solution.values, vec![0]);
+    }
+
+    #[test]
+    fn skips_looping_candidates() {
+        // Loops forever unless the input is 5, then outputs it
+        let program = vec![3, 12, 1008, 12, 5, 13, 1006, 13, 2, 4, 12, 99, 0, 0];
+        let mut computer = SymbolicComputer::new(program);
+        computer.unknown_input(3..=6);
+
+        let solution = computer.solve(Target::Output(0), 5).unwrap();
+        assert_eq!(solution.method, Method::Search);
+        assert_eq!(solution.values, vec![5]);
+    }
+
+    #[test]
+    fn reports_addresses_out_of_range() {
+        // Outputs the cell the input points at
+        let mut computer = SymbolicComputer::new(vec![3, 3, 4, 0, 99]);
+        computer.input(-1);
+        assert!(matches!(
+            computer.run(),
+            Err(SymbolicError::AddressOutOfRange(2))
+        ));
+
+        // A candidate that reads out of range misses rather than panicking
+        let mut computer = SymbolicComputer::new(vec![3, 3, 4, 0, 99]);
+        computer.unknown_input(-2..=2);
+        let solution = computer.solve(Target::Output(0), 4).unwrap();
+        assert_eq!(solution.values, vec![2]);
+
+        let mut computer = SymbolicComputer::new(NOUN_VERB.to_vec());
+        computer.unknown_cell(100_000, 0..=1);
+        assert!(matches!(
+            computer.run(),
+            Err(SymbolicError::AddressOutOfRange(100_000))
+        ));
+    }
+
+    #[test]
+    fn leaves_overflows_to_the_search() {
+        // Outputs a * 100000 * 100000, whose coefficient doesn't fit in an i32
+        let program = vec![
+            3, 13, 1002, 13, 100000, 13, 1002, 13, 100000, 13, 4, 13, 99, 0,
+        ];
+        let mut computer = SymbolicComputer::new(program);
+        computer.unknown_input(0..=0);
+        computer.run().unwrap();
+        assert!(computer.outputs()[0].linear(1).is_none());
+
+        let solution = computer.solve(Target::Output(0), 0).unwrap();
+        assert_eq!(solution.method, Method::Search);
+        assert_eq!(solution.values, vec![0]);
+
+        // Outputs a + 5, which can't reach a target below i32::MIN + 5
+        let mut computer = SymbolicComputer::new(vec![3, 7, 1001, 7, 5, 7, 4, 7, 99]);
+        computer.unknown_input(0..=3);
+        assert!(matches!(
+            computer.solve(Target::Output(0), i32::MIN),
+            Err(SymbolicError::NoSolution)
+        ));
+
+        assert!(Expr::<i32>::Unknown(3).linear(2).is_none());
+    }
+
+    #[test]
+    fn ranges_end_at_the_maximum() {
+        assert_eq!(
+            values_in(&(i32::MAX - 2..=i32::MAX)),
+            vec![i32::MAX - 2, i32::MAX - 1, i32::MAX]
+        );
+        assert_eq!(values_in(&(i8::MIN..=i8::MAX)).len(), 256);
+    }
+
+    #[test]
+    fn reports_unreachable_targets() {
+        let mut computer = SymbolicComputer::new(NOUN_VERB.to_vec());
+        computer.unknown_cell(1, 0..=9);
+        computer.unknown_cell(2, 0..=9);
+
+        assert!(matches!(
+            computer.solve(Target::Cell(0), 1234),
+            Err(SymbolicError::NoSolution)
+        ));
+    }
+}
@@ -2,131 +2,138 @@
 extern crate enum_primitive_derive;
 extern crate num_traits;
 
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod days;
+pub mod examples;
+pub mod input;
 pub mod intcode;
-
-use std::{
-    fs::{self, File},
-    io::{self, BufRead, BufReader, Seek, Write},
-    net::TcpStream,
-    path::Path,
-};
-
-use native_tls::TlsConnector;
-
-#[macro_export]
-macro_rules! timed_main {
-    ($a: expr, $($body:tt)+) => {
-    use num_format::{Locale, ToFormattedString};
-
-        fn main() {
-            let args: Vec<String> = std::env::args().collect();
-
-            if args.len() > 2 {
-                println!("Usage: <day> <number of iterations>");
-                std::process::exit(1);
-            }
-
-            let loops = if args.len() == 1 {
-                $a
-            } else if let Ok(num) = args[1].parse::<u128>() {
-                num
-            } else {
-                println!("Invalid number of iterations, continuing with default..");
-                println!("Usage: <day> <number of iterations>");
-                $a
-            };
-
-            let start = std::time::Instant::now();
-            for i in 0..loops {
-                let (p1, p2) = { $($body)+ };
-
-                if i == 0 {
-                    println!("Part 1 answer: {}", p1);
-                    println!("Part 2 answer: {}", p2);
-                }
-            }
-            let time = start.elapsed().as_nanos();
-            let locale = Locale::en_GB;
-            println!("Total time for {} iterations: {}ns", loops, time.to_formatted_string(&locale));
-            println!("Average time for {} iterations: {}ns", loops, (time / loops).to_formatted_string(&locale));
-        }
-    };
+pub mod memory;
+pub mod ocr;
+pub mod parallel;
+pub mod parse;
+pub mod submit;
+
+use std::{any::Any, fmt::Display};
+
+pub use input::{get_event_input, get_input, load_program, Event, InputError, InputSource};
+pub use parse::ParseError;
+
+// What a part with no puzzle answer gives, like part 2 of the last day. It's never recorded,
+// verified or submitted.
+pub const NO_ANSWER: &str = "-";
+
+pub trait Solution {
+    const YEAR: u16 = input::YEAR;
+    const DAY: u8;
+    // Number of timed iterations when none are asked for
+    const ITERATIONS: usize = 1;
+
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+
+    // Anything worth seeing besides the answers, like a map, for `aoc --show`
+    fn show(_input: &Self::Input) -> Option<String> {
+        None
+    }
 }
 
-// Functions for fetching and caching input, borrowed from BeniotZugmeyer/RustyAdventOfCode
-
-fn fetch_aoc(path: &str) -> impl Iterator<Item = String> {
-    let session = std::env::var("AOC_SESSION").expect("AOC_SESSION variable is missing");
-    let connector = TlsConnector::new().expect("Couldn't create a TLS connector");
-    let stream =
-        TcpStream::connect("adventofcode.com:443").expect("Failed to connect to the server");
-    let mut stream = connector
-        .connect("adventofcode.com", stream)
-        .expect("Couldn't connect to server with TLS");
+// Object-safe view of a Solution, so days with different input and answer types can share
+// a registry
+pub trait AnySolution: Sync {
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    fn iterations(&self) -> usize;
+    fn parse(&self, lines: &[String]) -> Result<Box<dyn Any>, ParseError>;
+    fn part1(&self, input: &dyn Any) -> String;
+    fn part2(&self, input: &dyn Any) -> String;
+    fn show(&self, input: &dyn Any) -> Option<String>;
+}
 
-    let request = &format!(
-        "\
-         GET {} HTTP/1.0\r\n\
-         Cookie: session={}\r\n\
-         \r\n\
-         ",
-        path, session
-    )
-    .into_bytes();
+impl<S> AnySolution for S
+where
+    S: Solution + Sync,
+    S::Input: 'static,
+{
+    fn year(&self) -> u16 {
+        S::YEAR
+    }
 
-    stream.write_all(request).unwrap();
+    fn day(&self) -> u8 {
+        S::DAY
+    }
 
-    let reader = BufReader::new(stream);
-    let mut lines = reader.lines().filter_map(Result::ok);
+    fn iterations(&self) -> usize {
+        S::ITERATIONS
+    }
 
-    let status = lines.next().expect("Empty response from the server");
+    fn parse(&self, lines: &[String]) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(S::parse(lines)?))
+    }
 
-    if status.ends_with("404 Not Found") {
-        panic!("Got a 404 for {}", path)
+    fn part1(&self, input: &dyn Any) -> String {
+        S::part1(
+            input
+                .downcast_ref()
+                .expect("input parsed for a different day"),
+        )
+        .to_string()
     }
 
-    lines.skip_while(|line| !line.is_empty()).skip(1)
-}
+    fn part2(&self, input: &dyn Any) -> String {
+        S::part2(
+            input
+                .downcast_ref()
+                .expect("input parsed for a different day"),
+        )
+        .to_string()
+    }
 
-fn from_cache<T: FnOnce() -> Option<Vec<String>>>(
-    name: &str,
-    factory: T,
-) -> Option<impl Iterator<Item = String>> {
-    let cache_dir = Path::new("../cache");
-    fs::create_dir_all(cache_dir).expect("Failed to create cache dir");
-
-    let file_cache_path = cache_dir.join(name);
-    let file = match File::open(&file_cache_path) {
-        Err(error) if error.kind() == io::ErrorKind::NotFound => {
-            if let Some(result) = factory() {
-                // Populate cache
-                let mut file = fs::OpenOptions::new()
-                    .read(true)
-                    .write(true)
-                    .create(true)
-                    .truncate(true)
-                    .open(&file_cache_path)
-                    .expect("Failed to open cache file for writing");
-                file.write_all(result.join("\n").as_bytes())
-                    .expect("Failed to write cache");
-                file.seek(io::SeekFrom::Start(0))
-                    .expect("Failed to seek file cache");
-                file
-            } else {
-                return None;
-            }
-        }
-        Err(error) => panic!("Failed to open cache file: {}", error),
-        Ok(file) => file,
-    };
-
-    let reader = BufReader::new(file);
-    Some(reader.lines().filter_map(Result::ok))
+    fn show(&self, input: &dyn Any) -> Option<String> {
+        S::show(
+            input
+                .downcast_ref()
+                .expect("input parsed for a different day"),
+        )
+    }
 }
 
-pub fn get_input(day: u8) -> impl Iterator<Item = String> {
-    from_cache(&format!("day_{:02}_input", day), move || {
-        Some(fetch_aoc(&format!("/2019/day/{}/input", day)).collect())
-    })
-    .unwrap()
+pub const SOLUTIONS: &[&dyn AnySolution] = &[
+    &days::day_01::Day01,
+    &days::day_02::Day02,
+    &days::day_03::Day03,
+    &days::day_04::Day04,
+    &days::day_05::Day05,
+    &days::day_06::Day06,
+    &days::day_07::Day07,
+    &days::day_08::Day08,
+    &days::day_09::Day09,
+    &days::day_10::Day10,
+    &days::day_11::Day11,
+    &days::day_12::Day12,
+    &days::day_13::Day13,
+    &days::day_14::Day14,
+    &days::day_15::Day15,
+    &days::day_16::Day16,
+    &days::day_17::Day17,
+    &days::day_18::Day18,
+    &days::day_19::Day19,
+    &days::day_20::Day20,
+    &days::day_21::Day21,
+    &days::day_22::Day22,
+    &days::day_24::Day24,
+    &days::day_25::Day25,
+];
+
+pub fn solution(year: u16, day: u8) -> Option<&'static dyn AnySolution> {
+    SOLUTIONS
+        .iter()
+        .copied()
+        .find(|s| s.year() == year && s.day() == day)
 }
@@ -0,0 +1,118 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+// Heap usage counting, for binaries that install the allocator:
+//
+//     #[global_allocator]
+//     static ALLOCATOR: CountingAllocator = CountingAllocator;
+//
+// The counters are shared by every thread, so measurements include threads a solution spawns,
+// but also anything else running at the same time.
+
+pub struct CountingAllocator;
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Usage {
+    pub allocations: u64,
+    // Total bytes asked for, including reallocations
+    pub allocated: u64,
+    // Most bytes in use at once, above what was in use beforehand
+    pub peak: u64,
+}
+
+fn record_allocation(size: usize) {
+    COUNTING.store(true, Ordering::Relaxed);
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED.fetch_add(size as u64, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CURRENT.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+            record_allocation(new_size);
+        }
+        new_ptr
+    }
+}
+
+// Whether the counting allocator is installed, which it must have been if anything's been
+// allocated through it
+pub fn is_counting() -> bool {
+    drop(Box::new(0u8));
+    COUNTING.load(Ordering::Relaxed)
+}
+
+// Runs the function, counting what it allocates
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+
+    let result = f();
+
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        allocated: ALLOCATED.load(Ordering::Relaxed) - allocated,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(baseline),
+    };
+    (result, usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn counts_allocations() {
+        assert!(is_counting());
+
+        // Other tests allocate at the same time, so these are lower bounds
+        let (sum, usage) = measure(|| {
+            let mut v = Vec::with_capacity(1000);
+            v.extend(0..1000u64);
+            let doubled = v.iter().map(|x| x * 2).collect::<Vec<_>>();
+            doubled.iter().sum::<u64>()
+        });
+        assert_eq!(sum, 999_000);
+        assert!(usage.allocations >= 2);
+        assert!(usage.allocated >= 16_000);
+        assert!(usage.peak >= 16_000);
+    }
+}
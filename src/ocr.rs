@@ -0,0 +1,82 @@
+// Reads the block capitals some puzzles draw: 4 pixels wide and 6 tall, with a 1 pixel gap
+// between letters
+
+const HEIGHT: usize = 6;
+const WIDTH: usize = 4;
+
+const GLYPHS: &[(char, [&str; HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+// Reads the letters from rows of lit pixels, starting at the top left. Returns None if any
+// letter isn't recognised.
+pub fn read_letters(rows: &[Vec<bool>]) -> Option<String> {
+    if rows.len() < HEIGHT {
+        return None;
+    }
+
+    let width = rows[..HEIGHT].iter().map(Vec::len).min()?;
+    (0..width)
+        .step_by(WIDTH + 1)
+        .map(|x| {
+            let cell = rows[..HEIGHT]
+                .iter()
+                .map(|row| {
+                    (x..x + WIDTH)
+                        .map(|i| if row.get(i) == Some(&true) { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>();
+
+            GLYPHS
+                .iter()
+                .find(|(_, glyph)| glyph.iter().zip(&cell).all(|(g, c)| g == c))
+                .map(|(letter, _)| *letter)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixels(rows: &[&str]) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn reads_letters() {
+        let rows = pixels(&[
+            "#### ###  #### #  # ###  ",
+            "#    #  #    # #  # #  # ",
+            "###  ###    #  #  # #  # ",
+            "#    #  #  #   #  # ###  ",
+            "#    #  # #    #  # # #  ",
+            "#### ###  ####  ##  #  # ",
+        ]);
+        assert_eq!(read_letters(&rows), Some("EBZUR".to_owned()));
+    }
+
+    #[test]
+    fn rejects_unknown_letters() {
+        let rows = pixels(&["#  #", " ## ", "    ", "    ", " ## ", "#  #"]);
+        assert_eq!(read_letters(&rows), None);
+    }
+}
@@ -0,0 +1,324 @@
+use std::{
+    any::Any,
+    fmt::Write,
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossbeam::channel::{bounded, unbounded, RecvTimeoutError};
+
+use crate::{
+    bench::{benchmark, Report},
+    AnySolution,
+};
+
+// Runs days on a pool of worker threads. Each day runs on a thread of its own, so a worker
+// can give up on a day that's taking too long. Rust can't kill a thread, so a timed out day
+// keeps going in the background until the process exits.
+
+#[derive(Debug, Clone)]
+pub enum DayStatus {
+    Finished(Box<Report>),
+    // Couldn't load or parse the input, or panicked
+    Failed(String),
+    TimedOut,
+}
+
+#[derive(Debug, Clone)]
+pub struct DayResult {
+    pub year: u16,
+    pub day: u8,
+    pub status: DayStatus,
+    // Wall-clock time including loading the input
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Settings {
+    pub jobs: usize,
+    pub timeout: Duration,
+    // Overrides each day's own number of iterations
+    pub iterations: Option<usize>,
+    pub warmup: usize,
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => (*message).to_owned(),
+            Err(_) => "panicked".to_owned(),
+        },
+    }
+}
+
+fn run_day<L>(solution: &'static dyn AnySolution, load: Arc<L>, settings: Settings) -> DayResult
+where
+    L: Fn(&dyn AnySolution) -> Result<Vec<String>, String> + Send + Sync + 'static,
+{
+    let start = Instant::now();
+    let (sender, receiver) = bounded(1);
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let lines = load(solution)?;
+            let iterations = settings.iterations.unwrap_or_else(|| solution.iterations());
+            // Other days allocate at the same time, so heap usage would be meaningless
+            benchmark(solution, &lines, iterations, settings.warmup, false)
+                .map_err(|e| e.to_string())
+        }));
+        // Nobody's listening any more if the day timed out
+        let _ = sender.send(result.unwrap_or_else(|payload| Err(panic_message(payload))));
+    });
+
+    let status = match receiver.recv_timeout(settings.timeout) {
+        Ok(Ok(report)) => DayStatus::Finished(Box::new(report)),
+        Ok(Err(e)) => DayStatus::Failed(e),
+        Err(RecvTimeoutError::Timeout) => DayStatus::TimedOut,
+        Err(RecvTimeoutError::Disconnected) => DayStatus::Failed("panicked".to_owned()),
+    };
+    DayResult {
+        year: solution.year(),
+        day: solution.day(),
+        status,
+        elapsed: start.elapsed(),
+    }
+}
+
+// Runs every solution, returning the results in the order the solutions were given
+pub fn run_parallel<L>(
+    solutions: &[&'static dyn AnySolution],
+    load: L,
+    settings: Settings,
+) -> Vec<DayResult>
+where
+    L: Fn(&dyn AnySolution) -> Result<Vec<String>, String> + Send + Sync + 'static,
+{
+    let load = Arc::new(load);
+    let (job_sender, job_receiver) = unbounded();
+    let (result_sender, result_receiver) = unbounded();
+    for job in solutions.iter().copied().enumerate() {
+        job_sender.send(job).unwrap();
+    }
+    drop(job_sender);
+
+    let workers = (0..settings.jobs.max(1).min(solutions.len()))
+        .map(|_| {
+            let (jobs, results, load) = (job_receiver.clone(), result_sender.clone(), load.clone());
+            thread::spawn(move || {
+                for (i, solution) in jobs {
+                    results
+                        .send((i, run_day(solution, load.clone(), settings)))
+                        .unwrap();
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    drop(result_sender);
+
+    let mut results = result_receiver.iter().collect::<Vec<_>>();
+    for worker in workers {
+        worker.join().expect("worker thread panicked");
+    }
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+// e.g. 950ns, 12.3µs, 4.56ms, 1.20s
+fn short_duration(ns: f64) -> String {
+    if ns < 1e3 {
+        format!("{:.0}ns", ns)
+    } else if ns < 1e6 {
+        format!("{:.1}µs", ns / 1e3)
+    } else if ns < 1e9 {
+        format!("{:.2}ms", ns / 1e6)
+    } else {
+        format!("{:.2}s", ns / 1e9)
+    }
+}
+
+pub fn summary_table(results: &[DayResult], timeout: Duration) -> String {
+    let answer_width = results
+        .iter()
+        .filter_map(|result| match &result.status {
+            DayStatus::Finished(report) => report.answers.iter().map(String::len).max(),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0)
+        .max("part 2".len());
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{:<5} {:<w$} {:<w$} {:>10} {:>10} {:>10} {:>10}  status",
+        "day",
+        "part 1",
+        "part 2",
+        "parse",
+        "part 1",
+        "part 2",
+        "wall",
+        w = answer_width
+    )
+    .unwrap();
+
+    for result in results {
+        let wall = short_duration(result.elapsed.as_nanos() as f64);
+        match &result.status {
+            DayStatus::Finished(report) => writeln!(
+                out,
+                "{:<5} {:<w$} {:<w$} {:>10} {:>10} {:>10} {:>10}  ok",
+                format!("{:02}", result.day),
+                report.answers[0],
+                report.answers[1],
+                short_duration(report.parse.mean),
+                short_duration(report.part1.mean),
+                short_duration(report.part2.mean),
+                wall,
+                w = answer_width
+            ),
+            status => writeln!(
+                out,
+                "{:<5} {:<w$} {:<w$} {:>10} {:>10} {:>10} {:>10}  {}",
+                format!("{:02}", result.day),
+                "-",
+                "-",
+                "-",
+                "-",
+                "-",
+                wall,
+                match status {
+                    DayStatus::Failed(e) => format!("failed: {}", e),
+                    _ => format!("timed out after {}s", timeout.as_secs_f64()),
+                },
+                w = answer_width
+            ),
+        }
+        .unwrap();
+    }
+
+    let count = |f: fn(&DayStatus) -> bool| results.iter().filter(|r| f(&r.status)).count();
+    writeln!(
+        out,
+        "{} days, {} failed, {} timed out",
+        results.len(),
+        count(|s| matches!(s, DayStatus::Failed(_))),
+        count(|s| matches!(s, DayStatus::TimedOut))
+    )
+    .unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParseError, Solution};
+
+    struct Quick;
+    struct Panics;
+    struct Hangs;
+
+    impl Solution for Quick {
+        const DAY: u8 = 1;
+        type Input = usize;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(lines: &[String]) -> Result<usize, ParseError> {
+            Ok(lines.len())
+        }
+        fn part1(input: &usize) -> usize {
+            input * 2
+        }
+        fn part2(input: &usize) -> usize {
+            input * 3
+        }
+    }
+
+    impl Solution for Panics {
+        const DAY: u8 = 2;
+        type Input = ();
+        type Part1 = u8;
+        type Part2 = u8;
+
+        fn parse(_: &[String]) -> Result<(), ParseError> {
+            Ok(())
+        }
+        fn part1(_: &()) -> u8 {
+            panic!("no solution")
+        }
+        fn part2(_: &()) -> u8 {
+            0
+        }
+    }
+
+    impl Solution for Hangs {
+        const DAY: u8 = 3;
+        type Input = ();
+        type Part1 = u8;
+        type Part2 = u8;
+
+        fn parse(_: &[String]) -> Result<(), ParseError> {
+            Ok(())
+        }
+        fn part1(_: &()) -> u8 {
+            thread::sleep(Duration::from_secs(10));
+            0
+        }
+        fn part2(_: &()) -> u8 {
+            0
+        }
+    }
+
+    #[test]
+    fn runs_days_with_timeouts() {
+        let solutions: &[&'static dyn AnySolution] = &[&Hangs, &Quick, &Panics, &Quick];
+        let settings = Settings {
+            jobs: 2,
+            timeout: Duration::from_millis(200),
+            iterations: Some(3),
+            warmup: 0,
+        };
+        let start = Instant::now();
+        let results = run_parallel(
+            solutions,
+            |_: &dyn AnySolution| Ok(vec![String::new(); 7]),
+            settings,
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        let days = results.iter().map(|r| r.day).collect::<Vec<_>>();
+        assert_eq!(days, vec![3, 1, 2, 1]);
+        assert!(matches!(results[0].status, DayStatus::TimedOut));
+        match &results[1].status {
+            DayStatus::Finished(report) => assert_eq!(report.answers, ["14", "21"]),
+            status => panic!("expected day 1 to finish, got {:?}", status),
+        }
+        match &results[2].status {
+            DayStatus::Failed(e) => assert_eq!(e, "no solution"),
+            status => panic!("expected day 2 to fail, got {:?}", status),
+        }
+
+        let table = summary_table(&results, settings.timeout);
+        assert!(table.contains("failed: no solution"));
+        assert!(table.ends_with("4 days, 1 failed, 1 timed out\n"));
+    }
+
+    #[test]
+    fn reports_input_errors() {
+        let settings = Settings {
+            jobs: 4,
+            timeout: Duration::from_secs(5),
+            iterations: None,
+            warmup: 0,
+        };
+        let results = run_parallel(
+            &[&Quick],
+            |_: &dyn AnySolution| Err("no input".to_owned()),
+            settings,
+        );
+        assert!(matches!(&results[0].status, DayStatus::Failed(e) if e == "no input"));
+    }
+}
@@ -0,0 +1,315 @@
+use std::{any::type_name, error::Error, fmt, iter, str::FromStr};
+
+// Parsers for puzzle input that say where the input went wrong, rather than panicking
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    // Both 1-based, with columns counted in characters
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+// Part of a line of input, remembering where it starts so errors can point at it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span<'a> {
+    pub text: &'a str,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl<'a> Span<'a> {
+    pub fn new(text: &'a str, line: usize) -> Self {
+        Span {
+            text,
+            line,
+            column: 1,
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn error<S: Into<String>>(&self, message: S) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        }
+    }
+
+    // The part between two byte offsets
+    fn slice(&self, start: usize, end: usize) -> Span<'a> {
+        Span {
+            text: &self.text[start..end],
+            line: self.line,
+            column: self.column + self.text[..start].chars().count(),
+        }
+    }
+
+    // Where the span ends, for errors about something missing
+    fn end(&self) -> Span<'a> {
+        self.slice(self.text.len(), self.text.len())
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        let start = self.text.len() - self.text.trim_start().len();
+        self.slice(start, start + self.text.trim().len())
+    }
+
+    pub fn split(self, separator: &'a str) -> impl Iterator<Item = Span<'a>> {
+        let mut start = 0;
+        self.text
+            .match_indices(separator)
+            .map(|(i, _)| i)
+            .chain(iter::once(self.text.len()))
+            .map(move |end| {
+                let span = self.slice(start, end);
+                start = end + separator.len();
+                span
+            })
+    }
+
+    pub fn split_once(&self, separator: &str) -> Result<(Span<'a>, Span<'a>), ParseError> {
+        match self.text.find(separator) {
+            Some(i) => Ok((
+                self.slice(0, i),
+                self.slice(i + separator.len(), self.text.len()),
+            )),
+            None => Err(self.error(format!("expected {:?} in {:?}", separator, self.text))),
+        }
+    }
+
+    pub fn strip_prefix(&self, prefix: &str) -> Result<Span<'a>, ParseError> {
+        if self.text.starts_with(prefix) {
+            Ok(self.slice(prefix.len(), self.text.len()))
+        } else {
+            Err(self.error(format!("expected {:?}", prefix)))
+        }
+    }
+
+    pub fn strip_suffix(&self, suffix: &str) -> Result<Span<'a>, ParseError> {
+        if self.text.ends_with(suffix) {
+            Ok(self.slice(0, self.text.len() - suffix.len()))
+        } else {
+            Err(self.end().error(format!("expected {:?}", suffix)))
+        }
+    }
+
+    // The first character and the rest
+    pub fn split_first(&self) -> Result<(char, Span<'a>), ParseError> {
+        match self.text.chars().next() {
+            Some(c) => Ok((c, self.slice(c.len_utf8(), self.text.len()))),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    pub fn parse<T: FromStr>(&self) -> Result<T, ParseError> {
+        self.text.parse().map_err(|_| {
+            // e.g. alloc::string::String
+            let name = type_name::<T>().rsplit("::").next().unwrap_or("value");
+            self.error(format!("expected {}, found {:?}", name, self.text))
+        })
+    }
+}
+
+// Each line of the input, numbered from 1
+pub fn lines(input: &[String]) -> impl Iterator<Item = Span<'_>> {
+    input
+        .iter()
+        .enumerate()
+        .map(|(i, line)| Span::new(line, i + 1))
+}
+
+pub fn single_line(input: &[String]) -> Result<Span<'_>, ParseError> {
+    let mut lines = lines(input);
+    let line = lines
+        .next()
+        .ok_or_else(|| Span::new("", 1).error("expected a line of input"))?;
+    match lines.next() {
+        Some(extra) => Err(extra.error("expected a single line of input")),
+        None => Ok(line),
+    }
+}
+
+// e.g. `1,2,-3` with a separator of `,`
+pub fn int_list<T: FromStr>(span: Span, separator: &str) -> Result<Vec<T>, ParseError> {
+    span.split(separator)
+        .map(|item| item.trim().parse())
+        .collect()
+}
+
+pub fn ints_per_line<T: FromStr>(input: &[String]) -> Result<Vec<T>, ParseError> {
+    lines(input).map(|line| line.trim().parse()).collect()
+}
+
+// e.g. `0123`
+pub fn digits(span: Span) -> Result<Vec<u8>, ParseError> {
+    span.text
+        .char_indices()
+        .map(|(i, c)| {
+            c.to_digit(10).map(|d| d as u8).ok_or_else(|| {
+                span.slice(i, i)
+                    .error(format!("expected a digit, found {:?}", c))
+            })
+        })
+        .collect()
+}
+
+// Rows of cells, which all have to be as wide as the first
+pub fn grid<T, F>(input: &[String], cell: F) -> Result<Vec<Vec<T>>, ParseError>
+where
+    F: Fn(char) -> Option<T>,
+{
+    let mut rows: Vec<Vec<T>> = Vec::new();
+    for line in lines(input) {
+        let row = line
+            .text
+            .char_indices()
+            .map(|(i, c)| {
+                cell(c).ok_or_else(|| line.slice(i, i).error(format!("unexpected {:?}", c)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(first) = rows.first().filter(|first| first.len() != row.len()) {
+            return Err(line.error(format!(
+                "expected {} cells like the first row, found {}",
+                first.len(),
+                row.len()
+            )));
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+// e.g. `COM)B` with a separator of `)`, where neither side can be empty
+pub fn key_value<'a>(span: Span<'a>, separator: &str) -> Result<(Span<'a>, Span<'a>), ParseError> {
+    let (key, value) = span.split_once(separator)?;
+    if key.text.is_empty() {
+        return Err(key.error(format!("expected something before {:?}", separator)));
+    }
+    if value.text.is_empty() {
+        return Err(value.error(format!("expected something after {:?}", separator)));
+    }
+    Ok((key, value))
+}
+
+// A quantity and a name, e.g. `7 ORE`
+pub fn term<T: FromStr>(span: Span) -> Result<(T, Span), ParseError> {
+    let (quantity, name) = span.trim().split_once(" ")?;
+    let quantity = quantity.parse()?;
+    if name.text.is_empty() || !name.text.chars().all(char::is_alphanumeric) {
+        return Err(name.error(format!("expected a name, found {:?}", name.text)));
+    }
+    Ok((quantity, name))
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|&line| line.to_owned()).collect()
+    }
+
+    fn position(error: ParseError) -> (usize, usize) {
+        (error.line, error.column)
+    }
+
+    #[test]
+    fn parses_int_lists() {
+        let program = input(&["1,-2, 3"]);
+        let line = single_line(&program).unwrap();
+        assert_eq!(int_list::<i32>(line, ","), Ok(vec![1, -2, 3]));
+
+        let program = input(&["1,2,x,4"]);
+        let error = int_list::<i32>(single_line(&program).unwrap(), ",").unwrap_err();
+        assert_eq!(position(error.clone()), (1, 5));
+        assert_eq!(error.message, "expected i32, found \"x\"");
+
+        assert!(single_line(&[]).is_err());
+        assert_eq!(
+            single_line(&input(&["1", "2"])).map_err(position),
+            Err((2, 1))
+        );
+        assert_eq!(
+            ints_per_line::<u32>(&input(&["12", "", "14"])).map_err(position),
+            Err((2, 1))
+        );
+    }
+
+    #[test]
+    fn parses_grids() {
+        let cell = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        assert_eq!(
+            grid(&input(&["#.", ".#"]), cell),
+            Ok(vec![vec![true, false], vec![false, true]])
+        );
+        assert_eq!(
+            grid(&input(&["#.", ".x"]), cell).map_err(position),
+            Err((2, 2))
+        );
+        assert_eq!(
+            grid(&input(&["#.", "."]), cell).map_err(position),
+            Err((2, 1))
+        );
+    }
+
+    #[test]
+    fn parses_pairs_and_terms() {
+        let line = Span::new("COM)B", 3);
+        let (key, value) = key_value(line, ")").unwrap();
+        assert_eq!((key.as_str(), value.as_str()), ("COM", "B"));
+        assert_eq!(value.column, 5);
+        assert_eq!(key_value(line, "=").map_err(position), Err((3, 1)));
+        assert_eq!(
+            key_value(Span::new("COM)", 1), ")").map_err(position),
+            Err((1, 5))
+        );
+
+        let reaction = Span::new("7 A, 12 ORE", 1);
+        let terms = reaction
+            .split(", ")
+            .map(term::<u64>)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(terms[1].0, 12);
+        assert_eq!((terms[1].1.as_str(), terms[1].1.column), ("ORE", 9));
+        assert!(term::<u64>(Span::new("7", 1)).is_err());
+        assert!(term::<u64>(Span::new("x ORE", 1)).is_err());
+    }
+
+    #[test]
+    fn strips_and_splits() {
+        let span = Span::new("<x=1>", 1);
+        let inner = span.strip_prefix("<").unwrap().strip_suffix(">").unwrap();
+        assert_eq!((inner.as_str(), inner.column), ("x=1", 2));
+        assert_eq!(
+            Span::new("<x=1", 4).strip_suffix(">").map_err(position),
+            Err((4, 5))
+        );
+        assert_eq!(
+            Span::new("", 1).split_first().map_err(position),
+            Err((1, 1))
+        );
+        assert_eq!(digits(Span::new("12a", 1)).map_err(position), Err((1, 3)));
+    }
+}
@@ -0,0 +1,328 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::client::{Client, FetchError};
+
+// Every answer that's been submitted, one per line as `<year> <day> <part> <outcome> <answer>`
+pub const DEFAULT_LOG_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/submissions.txt");
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    // How long to wait, as the server words it, e.g. "4m 37s"
+    RateLimited(Option<String>),
+    // The part was solved already, or part 1 hasn't been yet
+    WrongLevel,
+    Unrecognised,
+}
+
+// What's already known about answers, so wrong ones aren't sent twice
+#[derive(Debug)]
+pub struct SubmissionLog {
+    path: PathBuf,
+    outcomes: HashMap<(u16, u8, u8), Vec<(String, Outcome)>>,
+}
+
+impl Outcome {
+    // Reads the outcome out of the page the server responds with
+    pub fn parse(page: &str) -> Self {
+        let page = page.to_lowercase();
+        if page.contains("that's the right answer") {
+            Outcome::Correct
+        } else if page.contains("that's not the right answer") {
+            if page.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if page.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Incorrect
+            }
+        } else if page.contains("you gave an answer too recently") {
+            // e.g. "You have 4m 37s left to wait."
+            let wait = page
+                .split_once("you have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| wait.to_owned());
+            Outcome::RateLimited(wait)
+        } else if page.contains("you don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            Outcome::Unrecognised
+        }
+    }
+
+    // Only these say anything about the answer itself
+    fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Correct | Outcome::Incorrect | Outcome::TooHigh | Outcome::TooLow
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::Incorrect => "incorrect",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::RateLimited(_) => "rate-limited",
+            Outcome::WrongLevel => "wrong-level",
+            Outcome::Unrecognised => "unrecognised",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "correct" => Some(Outcome::Correct),
+            "incorrect" => Some(Outcome::Incorrect),
+            "too-high" => Some(Outcome::TooHigh),
+            "too-low" => Some(Outcome::TooLow),
+            _ => None,
+        }
+    }
+}
+
+impl SubmissionLog {
+    // A missing file is treated as having no submissions yet
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_owned();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let mut log = SubmissionLog {
+            path,
+            outcomes: HashMap::new(),
+        };
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(5, ' ');
+            let year = fields.next().and_then(|y| y.parse::<u16>().ok());
+            let day = fields.next().and_then(|d| d.parse::<u8>().ok());
+            let part = fields.next().and_then(|p| p.parse::<u8>().ok());
+            let outcome = fields.next().and_then(Outcome::from_name);
+            match (year, day, part, outcome, fields.next()) {
+                (Some(year), Some(day), Some(part @ 1..=2), Some(outcome), Some(answer)) => {
+                    log.insert(year, day, part, answer, outcome)
+                }
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "{}:{}: invalid submission {:?}",
+                            log.path.display(),
+                            i + 1,
+                            line
+                        ),
+                    ))
+                }
+            }
+        }
+
+        Ok(log)
+    }
+
+    fn insert(&mut self, year: u16, day: u8, part: u8, answer: &str, outcome: Outcome) {
+        self.outcomes
+            .entry((year, day, part))
+            .or_default()
+            .push((answer.to_owned(), outcome));
+    }
+
+    // What submitting the answer would get, if it can be worked out from earlier submissions.
+    // A number at or past one that was too high (or too low) must be too high (or too low) too.
+    pub fn known(&self, year: u16, day: u8, part: u8, answer: &str) -> Option<Outcome> {
+        let submitted = self.outcomes.get(&(year, day, part))?;
+        if let Some((_, outcome)) = submitted.iter().find(|(a, _)| a == answer) {
+            return Some(outcome.clone());
+        }
+
+        let answer = answer.parse::<i128>().ok()?;
+        submitted.iter().find_map(|(a, outcome)| {
+            let a = a.parse::<i128>().ok()?;
+            match outcome {
+                Outcome::TooHigh if answer >= a => Some(Outcome::TooHigh),
+                Outcome::TooLow if answer <= a => Some(Outcome::TooLow),
+                _ => None,
+            }
+        })
+    }
+
+    // Appends the outcome to the log, unless it doesn't say whether the answer was right
+    pub fn record(
+        &mut self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+        outcome: &Outcome,
+    ) -> io::Result<()> {
+        if !outcome.is_final() {
+            return Ok(());
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(
+            file,
+            "{} {:02} {} {} {}",
+            year,
+            day,
+            part,
+            outcome.name(),
+            answer
+        )?;
+        self.insert(year, day, part, answer, outcome.clone());
+        Ok(())
+    }
+}
+
+// Percent-encodes everything but unreserved characters
+fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+// Posts the answer to the server. Checking the log first is up to the caller.
+pub fn submit(
+    client: &Client,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<Outcome, FetchError> {
+    let form = format!("level={}&answer={}", part, form_encode(answer));
+    let page = client.post_text(&format!("/{}/day/{}/answer", year, day), &form)?;
+    Ok(Outcome::parse(&page))
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "That's the right answer"),
+            Self::Incorrect => write!(f, "That's not the right answer"),
+            Self::TooHigh => write!(f, "That's not the right answer, it's too high"),
+            Self::TooLow => write!(f, "That's not the right answer, it's too low"),
+            Self::RateLimited(Some(wait)) => {
+                write!(f, "Answered too recently, try again in {}", wait)
+            }
+            Self::RateLimited(None) => write!(f, "Answered too recently, try again later"),
+            Self::WrongLevel => write!(f, "Already solved, or not unlocked yet"),
+            Self::Unrecognised => write!(f, "Couldn't make sense of the server's response"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::{mock_server, response};
+
+    fn page(message: &str) -> String {
+        format!(
+            "<!DOCTYPE html><main><article><p>{}</p></article></main>",
+            message
+        )
+    }
+
+    #[test]
+    fn parses_outcomes() {
+        let outcomes = [
+            (
+                "That's the right answer!  You are one gold star closer.",
+                Outcome::Correct,
+            ),
+            (
+                "That's not the right answer.  If you're stuck, ...",
+                Outcome::Incorrect,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                Outcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Outcome::TooLow,
+            ),
+            (
+                "You gave an answer too recently. You have 4m 37s left to wait.",
+                Outcome::RateLimited(Some("4m 37s".to_owned())),
+            ),
+            (
+                "You don't seem to be solving the right level.",
+                Outcome::WrongLevel,
+            ),
+            ("Something else entirely", Outcome::Unrecognised),
+        ];
+        for (message, outcome) in outcomes.iter() {
+            assert_eq!(Outcome::parse(&page(message)), *outcome, "{}", message);
+        }
+    }
+
+    #[test]
+    fn posts_answers() {
+        let (server, handle) = mock_server(vec![
+            response("200 OK", &page("That's the right answer!")),
+            response("200 OK", &page("<a href=\"/2019/auth/login\">[Log In]</a>")),
+        ]);
+        let client = Client::new(server, "abc123");
+
+        assert_eq!(
+            submit(&client, 2019, 8, 2, "EB Z+").unwrap(),
+            Outcome::Correct
+        );
+        assert!(matches!(
+            submit(&client, 2019, 8, 2, "EBZUR"),
+            Err(FetchError::NotLoggedIn)
+        ));
+
+        let requests = handle.join().unwrap();
+        assert!(requests[0].starts_with("POST /2019/day/8/answer HTTP/1.0\r\n"));
+        assert!(requests[0].contains("Cookie: session=abc123\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=EB%20Z%2B"));
+    }
+
+    #[test]
+    fn remembers_wrong_answers() {
+        let path = std::env::temp_dir().join(format!("aoc2019_submissions_{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut log = SubmissionLog::load(&path).unwrap();
+        log.record(2019, 1, 1, "5000", &Outcome::TooHigh).unwrap();
+        log.record(2019, 1, 1, "100", &Outcome::TooLow).unwrap();
+        log.record(2019, 1, 1, "1234", &Outcome::Incorrect).unwrap();
+        log.record(2019, 1, 1, "999", &Outcome::RateLimited(None))
+            .unwrap();
+
+        let log = SubmissionLog::load(&path).unwrap();
+        assert_eq!(log.known(2019, 1, 1, "1234"), Some(Outcome::Incorrect));
+        assert_eq!(log.known(2019, 1, 1, "6000"), Some(Outcome::TooHigh));
+        assert_eq!(log.known(2019, 1, 1, "42"), Some(Outcome::TooLow));
+        assert_eq!(log.known(2019, 1, 1, "999"), None);
+        assert_eq!(log.known(2019, 1, 2, "1234"), None);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,24 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+// Every line of `aoc --json` has to parse, so nothing else can be printed to stdout. Day 5 runs
+// Intcode diagnostics whose output used to be echoed.
+#[test]
+fn json_output_is_only_json() {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["--json", "--warmup", "0", "5", "2"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let reports = stdout
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0]["year"], 2019);
+    assert_eq!(reports[0]["day"], 5);
+    assert_eq!(reports[0]["part1"]["samples"], 2);
+}